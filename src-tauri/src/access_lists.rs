@@ -0,0 +1,227 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+use crate::{profiles, AppState};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WhitelistEntry {
+    pub uuid: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OpEntry {
+    pub uuid: String,
+    pub name: String,
+    pub level: u8,
+    pub bypasses_player_limit: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BanEntry {
+    pub uuid: String,
+    pub name: String,
+    pub created: String,
+    pub source: String,
+    /// A date in `DATE_FORMAT`, or `forever`.
+    pub expires: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IpBanEntry {
+    pub ip: String,
+    pub created: String,
+    pub source: String,
+    pub expires: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AccessLists {
+    pub whitelist_enabled: bool,
+    pub whitelist: Vec<WhitelistEntry>,
+    pub ops: Vec<OpEntry>,
+    pub banned_players: Vec<BanEntry>,
+    pub banned_ips: Vec<IpBanEntry>,
+}
+
+fn read_list<T: DeserializeOwned>(server_path: &str, file: &str) -> Result<Vec<T>, String> {
+    let path = Path::new(server_path).join(file);
+    if !path.exists() { return Ok(Vec::new()); }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    if content.trim().is_empty() { return Ok(Vec::new()); }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", file, e))
+}
+
+fn write_list<T: Serialize>(server_path: &str, file: &str, entries: &[T]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    fs::write(Path::new(server_path).join(file), json).map_err(|e| e.to_string())
+}
+
+pub(crate) fn validate_player_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > 16 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("'{}' is not a valid player name", name));
+    }
+    Ok(())
+}
+
+fn validate_expiry(expires: Option<String>) -> Result<String, String> {
+    match expires.map(|e| e.trim().to_string()).filter(|e| !e.is_empty() && e != "forever") {
+        Some(date) => chrono::DateTime::parse_from_str(&date, DATE_FORMAT).map(|_| date).map_err(|_| "Ban expiry must look like 2025-01-31 18:00:00 +0000".to_string()),
+        None => Ok("forever".into()),
+    }
+}
+
+/// Trims the reason and refuses line breaks, which would end the console command early.
+fn validate_reason(reason: Option<String>) -> Result<Option<String>, String> {
+    let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if reason.as_ref().is_some_and(|r| r.contains(['\r', '\n'])) { return Err("Ban reason must be a single line".into()); }
+    Ok(reason)
+}
+
+fn now() -> String {
+    chrono::Local::now().format(DATE_FORMAT).to_string()
+}
+
+/// After editing a list file, tells a running server to pick it up, or says how to.
+fn after_file_edit(state: &AppState, server_path: &str, file: &str, mut done: Vec<String>) -> Result<Vec<String>, String> {
+    if !crate::is_server_running(state, server_path) { return Ok(done); }
+    if file == "whitelist.json" {
+        match crate::run_live(state, server_path, vec!["whitelist reload".into()])? {
+            Some(sent) => done.extend(sent),
+            None => done.push("Run 'whitelist reload' in the server console to apply it".into()),
+        }
+    } else {
+        done.push(format!("The running server reads {} again only after a restart", file));
+    }
+    Ok(done)
+}
+
+#[tauri::command]
+pub async fn get_access_lists(server_path: String) -> Result<AccessLists, String> {
+    Ok(AccessLists {
+        whitelist_enabled: crate::load_properties(&server_path).get("white-list").is_some_and(|v| v == "true"),
+        whitelist: read_list(&server_path, "whitelist.json")?,
+        ops: read_list(&server_path, "ops.json")?,
+        banned_players: read_list(&server_path, "banned-players.json")?,
+        banned_ips: read_list(&server_path, "banned-ips.json")?,
+    })
+}
+
+#[tauri::command]
+pub async fn whitelist_add(server_path: String, name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    validate_player_name(&name)?;
+    if let Some(done) = crate::run_live(&state, &server_path, vec![format!("whitelist add {}", name)])? { return Ok(done); }
+    let player = profiles::resolve_name(&state, &server_path, &name).await?;
+    let mut list: Vec<WhitelistEntry> = read_list(&server_path, "whitelist.json")?;
+    if list.iter().any(|e| e.uuid == player.uuid) { return Ok(vec![format!("{} is already whitelisted", player.name)]); }
+    list.push(WhitelistEntry { uuid: player.uuid, name: player.name.clone() });
+    write_list(&server_path, "whitelist.json", &list)?;
+    after_file_edit(&state, &server_path, "whitelist.json", vec![format!("Added {} to whitelist.json", player.name)])
+}
+
+#[tauri::command]
+pub async fn whitelist_remove(server_path: String, name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    validate_player_name(&name)?;
+    if let Some(done) = crate::run_live(&state, &server_path, vec![format!("whitelist remove {}", name)])? { return Ok(done); }
+    let mut list: Vec<WhitelistEntry> = read_list(&server_path, "whitelist.json")?;
+    list.retain(|e| !e.name.eq_ignore_ascii_case(&name));
+    write_list(&server_path, "whitelist.json", &list)?;
+    after_file_edit(&state, &server_path, "whitelist.json", vec![format!("Removed {} from whitelist.json", name)])
+}
+
+/// Ops a player. The console `op` command always uses `op-permission-level`, so a custom `level`
+/// or `bypasses_player_limit` only takes effect when the server is stopped.
+#[tauri::command]
+pub async fn op_add(server_path: String, name: String, level: Option<u8>, bypasses_player_limit: bool, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    validate_player_name(&name)?;
+    let default_level = crate::load_properties(&server_path).get("op-permission-level").and_then(|v| v.parse().ok()).unwrap_or(4);
+    let level = level.unwrap_or(default_level);
+    if !(1..=4).contains(&level) { return Err("Op level must be between 1 and 4".into()); }
+    if let Some(mut done) = crate::run_live(&state, &server_path, vec![format!("op {}", name)])? {
+        if level != default_level || bypasses_player_limit {
+            done.push(format!("The console op command used level {}; set a custom level or player limit bypass while the server is stopped", default_level));
+        }
+        return Ok(done);
+    }
+    let player = profiles::resolve_name(&state, &server_path, &name).await?;
+    let mut list: Vec<OpEntry> = read_list(&server_path, "ops.json")?;
+    list.retain(|e| e.uuid != player.uuid);
+    list.push(OpEntry { uuid: player.uuid, name: player.name.clone(), level, bypasses_player_limit });
+    write_list(&server_path, "ops.json", &list)?;
+    after_file_edit(&state, &server_path, "ops.json", vec![format!("Added {} to ops.json at level {}", player.name, level)])
+}
+
+#[tauri::command]
+pub async fn op_remove(server_path: String, name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    validate_player_name(&name)?;
+    if let Some(done) = crate::run_live(&state, &server_path, vec![format!("deop {}", name)])? { return Ok(done); }
+    let mut list: Vec<OpEntry> = read_list(&server_path, "ops.json")?;
+    list.retain(|e| !e.name.eq_ignore_ascii_case(&name));
+    write_list(&server_path, "ops.json", &list)?;
+    after_file_edit(&state, &server_path, "ops.json", vec![format!("Removed {} from ops.json", name)])
+}
+
+/// Bans a player. Temporary bans (`expires`) can only be written while the server is stopped,
+/// since the `ban` command has no duration.
+#[tauri::command]
+pub async fn ban_player(server_path: String, name: String, reason: Option<String>, expires: Option<String>, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    validate_player_name(&name)?;
+    let expires = validate_expiry(expires)?;
+    let reason = validate_reason(reason)?;
+    if expires != "forever" && crate::is_server_running(&state, &server_path) {
+        return Err("Temporary bans can only be set while the server is stopped".into());
+    }
+    let command = match &reason { Some(r) => format!("ban {} {}", name, r), None => format!("ban {}", name) };
+    if let Some(done) = crate::run_live(&state, &server_path, vec![command])? { return Ok(done); }
+    let player = profiles::resolve_name(&state, &server_path, &name).await?;
+    let mut list: Vec<BanEntry> = read_list(&server_path, "banned-players.json")?;
+    list.retain(|e| e.uuid != player.uuid);
+    list.push(BanEntry { uuid: player.uuid, name: player.name.clone(), created: now(), source: "Server".into(), expires, reason: reason.unwrap_or_else(|| "Banned by an operator.".into()) });
+    write_list(&server_path, "banned-players.json", &list)?;
+    after_file_edit(&state, &server_path, "banned-players.json", vec![format!("Added {} to banned-players.json", player.name)])
+}
+
+#[tauri::command]
+pub async fn pardon_player(server_path: String, name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    validate_player_name(&name)?;
+    if let Some(done) = crate::run_live(&state, &server_path, vec![format!("pardon {}", name)])? { return Ok(done); }
+    let mut list: Vec<BanEntry> = read_list(&server_path, "banned-players.json")?;
+    list.retain(|e| !e.name.eq_ignore_ascii_case(&name));
+    write_list(&server_path, "banned-players.json", &list)?;
+    after_file_edit(&state, &server_path, "banned-players.json", vec![format!("Removed {} from banned-players.json", name)])
+}
+
+#[tauri::command]
+pub async fn ban_ip(server_path: String, ip: String, reason: Option<String>, expires: Option<String>, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let ip = ip.trim().parse::<std::net::IpAddr>().map_err(|_| format!("'{}' is not a valid IP address", ip))?.to_string();
+    let expires = validate_expiry(expires)?;
+    let reason = validate_reason(reason)?;
+    if expires != "forever" && crate::is_server_running(&state, &server_path) {
+        return Err("Temporary bans can only be set while the server is stopped".into());
+    }
+    let command = match &reason { Some(r) => format!("ban-ip {} {}", ip, r), None => format!("ban-ip {}", ip) };
+    if let Some(done) = crate::run_live(&state, &server_path, vec![command])? { return Ok(done); }
+    let mut list: Vec<IpBanEntry> = read_list(&server_path, "banned-ips.json")?;
+    list.retain(|e| e.ip != ip);
+    list.push(IpBanEntry { ip: ip.clone(), created: now(), source: "Server".into(), expires, reason: reason.unwrap_or_else(|| "Banned by an operator.".into()) });
+    write_list(&server_path, "banned-ips.json", &list)?;
+    after_file_edit(&state, &server_path, "banned-ips.json", vec![format!("Added {} to banned-ips.json", ip)])
+}
+
+#[tauri::command]
+pub async fn pardon_ip(server_path: String, ip: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let ip = ip.trim().parse::<std::net::IpAddr>().map_err(|_| format!("'{}' is not a valid IP address", ip))?.to_string();
+    if let Some(done) = crate::run_live(&state, &server_path, vec![format!("pardon-ip {}", ip)])? { return Ok(done); }
+    let mut list: Vec<IpBanEntry> = read_list(&server_path, "banned-ips.json")?;
+    list.retain(|e| e.ip != ip);
+    write_list(&server_path, "banned-ips.json", &list)?;
+    after_file_edit(&state, &server_path, "banned-ips.json", vec![format!("Removed {} from banned-ips.json", ip)])
+}
@@ -0,0 +1,247 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::{profiles, worlds, AppState};
+
+const CATEGORIES: [&str; 5] = ["story", "nether", "end", "adventure", "husbandry"];
+
+const V1_12: i64 = 1139;
+const V1_13: i64 = 1519;
+const V1_14: i64 = 1952;
+const V1_15: i64 = 2225;
+const V1_16: i64 = 2566;
+const V1_17: i64 = 2724;
+const V1_18: i64 = 2860;
+const V1_19: i64 = 3105;
+const V1_20: i64 = 3463;
+const V1_20_5: i64 = 3837;
+const V1_21: i64 = 3953;
+const V1_21_4: i64 = 4189;
+const V1_21_6: i64 = 4435;
+
+/// Vanilla advancements as `(id, added in, removed in)` data versions.
+const VANILLA: &[(&str, i64, Option<i64>)] = &[
+    ("story/root", V1_12, None), ("story/mine_stone", V1_12, None), ("story/upgrade_tools", V1_12, None),
+    ("story/smelt_iron", V1_12, None), ("story/obtain_armor", V1_12, None), ("story/lava_bucket", V1_12, None),
+    ("story/iron_tools", V1_12, None), ("story/deflect_arrow", V1_12, None), ("story/form_obsidian", V1_12, None),
+    ("story/mine_diamond", V1_12, None), ("story/enter_the_nether", V1_12, None), ("story/shiny_gear", V1_12, None),
+    ("story/enchant_item", V1_12, None), ("story/cure_zombie_villager", V1_12, None), ("story/follow_ender_eye", V1_12, None),
+    ("story/enter_the_end", V1_12, None),
+
+    ("nether/root", V1_12, None), ("nether/return_to_sender", V1_12, None), ("nether/find_fortress", V1_12, None),
+    ("nether/fast_travel", V1_12, None), ("nether/uneasy_alliance", V1_12, None), ("nether/get_wither_skull", V1_12, None),
+    ("nether/obtain_blaze_rod", V1_12, None), ("nether/summon_wither", V1_12, None), ("nether/brew_potion", V1_12, None),
+    ("nether/create_beacon", V1_12, None), ("nether/all_potions", V1_12, None), ("nether/create_full_beacon", V1_12, None),
+    ("nether/all_effects", V1_12, None), ("nether/find_bastion", V1_16, None), ("nether/obtain_ancient_debris", V1_16, None),
+    ("nether/obtain_crying_obsidian", V1_16, None), ("nether/distract_piglin", V1_16, None), ("nether/ride_strider", V1_16, None),
+    ("nether/loot_bastion", V1_16, None), ("nether/use_lodestone", V1_16, None), ("nether/netherite_armor", V1_16, None),
+    ("nether/charge_respawn_anchor", V1_16, None), ("nether/explore_nether", V1_16, None),
+    ("nether/ride_strider_in_overworld_lava", V1_17, None),
+
+    ("end/root", V1_12, None), ("end/kill_dragon", V1_12, None), ("end/dragon_egg", V1_12, None),
+    ("end/enter_end_gateway", V1_12, None), ("end/respawn_dragon", V1_12, None), ("end/dragon_breath", V1_12, None),
+    ("end/find_end_city", V1_12, None), ("end/elytra", V1_12, None), ("end/levitate", V1_12, None),
+
+    ("adventure/root", V1_12, None), ("adventure/kill_a_mob", V1_12, None), ("adventure/trade", V1_12, None),
+    ("adventure/sleep_in_bed", V1_12, None), ("adventure/shoot_arrow", V1_12, None), ("adventure/kill_all_mobs", V1_12, None),
+    ("adventure/totem_of_undying", V1_12, None), ("adventure/summon_iron_golem", V1_12, None), ("adventure/adventuring_time", V1_12, None),
+    ("adventure/sniper_duel", V1_12, None), ("adventure/throw_trident", V1_13, None), ("adventure/very_very_frightening", V1_13, None),
+    ("adventure/voluntary_exile", V1_14, None), ("adventure/ol_betsy", V1_14, None), ("adventure/hero_of_the_village", V1_14, None),
+    ("adventure/two_birds_one_arrow", V1_14, None), ("adventure/whos_the_pillager_now", V1_14, None), ("adventure/arbalistic", V1_14, None),
+    ("adventure/honey_block_slide", V1_15, None), ("adventure/bullseye", V1_16, None),
+    ("adventure/spyglass_at_parrot", V1_17, None), ("adventure/spyglass_at_ghast", V1_17, None), ("adventure/spyglass_at_dragon", V1_17, None),
+    ("adventure/lightning_rod_with_villager_no_fire", V1_17, None), ("adventure/walk_on_powder_snow_with_leather_boots", V1_17, None),
+    ("adventure/fall_from_world_height", V1_18, None), ("adventure/trade_at_world_height", V1_18, None),
+    ("adventure/play_jukebox_in_meadows", V1_18, None), ("adventure/avoid_vibration", V1_19, None),
+    ("adventure/kill_mob_near_sculk_catalyst", V1_19, None), ("adventure/read_power_of_chiseled_bookshelf", V1_20, None),
+    ("adventure/trim_with_any_armor_pattern", V1_20, None), ("adventure/trim_with_all_exclusive_armor_patterns", V1_20, None),
+    ("adventure/salvage_sherd", V1_20, None), ("adventure/craft_decorated_pot_using_only_sherds", V1_20, None),
+    ("adventure/crafters_crafting_crafters", V1_21, None), ("adventure/lighten_up", V1_21, None),
+    ("adventure/who_needs_rockets", V1_21, None), ("adventure/minecraft_trials_edition", V1_21, None),
+    ("adventure/under_lock_and_key", V1_21, None), ("adventure/revaulting", V1_21, None), ("adventure/blowback", V1_21, None),
+    ("adventure/overoverkill", V1_21, None), ("adventure/heart_transplanter", V1_21_4, None),
+
+    ("husbandry/root", V1_12, None), ("husbandry/plant_seed", V1_12, None), ("husbandry/breed_an_animal", V1_12, None),
+    ("husbandry/balanced_diet", V1_12, None), ("husbandry/break_diamond_hoe", V1_12, Some(V1_16)), ("husbandry/tame_an_animal", V1_12, None),
+    ("husbandry/bred_all_animals", V1_12, None), ("husbandry/fishy_business", V1_13, None), ("husbandry/tactical_fishing", V1_13, None),
+    ("husbandry/complete_catalogue", V1_14, None), ("husbandry/safely_harvest_honey", V1_15, None), ("husbandry/silk_touch_nest", V1_15, None),
+    ("husbandry/obtain_netherite_hoe", V1_16, None), ("husbandry/wax_on", V1_17, None), ("husbandry/wax_off", V1_17, None),
+    ("husbandry/axolotl_in_a_bucket", V1_17, None), ("husbandry/kill_axolotl_target", V1_17, None),
+    ("husbandry/make_a_sign_glow", V1_17, None), ("husbandry/ride_a_boat_with_a_goat", V1_17, None),
+    ("husbandry/allay_deliver_item_to_player", V1_19, None), ("husbandry/allay_deliver_cake_to_note_block", V1_19, None),
+    ("husbandry/tadpole_in_a_bucket", V1_19, None), ("husbandry/leash_all_frog_variants", V1_19, None),
+    ("husbandry/froglights", V1_19, None), ("husbandry/plant_any_sniffer_seed", V1_20, None),
+    ("husbandry/feed_snifflet", V1_20, None), ("husbandry/obtain_sniffer_egg", V1_20, None),
+    ("husbandry/repair_wolf_armor", V1_20_5, None), ("husbandry/remove_wolf_armor", V1_20_5, None),
+    ("husbandry/whole_pack", V1_20_5, None), ("husbandry/place_dried_ghast_in_water", V1_21_6, None),
+];
+
+#[derive(Serialize, Debug)]
+pub struct AdvancementProgress {
+    pub id: String,
+    pub category: String,
+    pub done: bool,
+    /// When the last criterion was met, in the game's `yyyy-MM-dd HH:mm:ss Z` format.
+    pub completed_at: Option<String>,
+    pub criteria_done: usize,
+    /// False for advancements added by datapacks or mods.
+    pub vanilla: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CategoryProgress {
+    pub category: String,
+    pub completed: usize,
+    pub total: usize,
+    pub percent: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlayerAdvancements {
+    pub uuid: String,
+    pub name: String,
+    pub data_version: Option<i64>,
+    pub categories: Vec<CategoryProgress>,
+    pub advancements: Vec<AdvancementProgress>,
+    /// Vanilla advancements for this version the player has not completed.
+    pub missing: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AdvancementSummary {
+    pub uuid: String,
+    pub name: String,
+    pub completed: usize,
+    pub total: usize,
+    pub percent: f64,
+}
+
+/// Vanilla advancement ids (`minecraft:story/root`, ...) that exist in the given data version.
+pub fn vanilla_ids(data_version: i64) -> Vec<String> {
+    VANILLA.iter()
+        .filter(|(_, since, until)| data_version >= *since && until.is_none_or(|u| data_version < u))
+        .map(|(id, _, _)| format!("minecraft:{}", id))
+        .collect()
+}
+
+fn advancements_dir(server_path: &str) -> PathBuf {
+    Path::new(server_path).join(worlds::active_world_name(server_path)).join("advancements")
+}
+
+fn category_of(id: &str) -> String {
+    let path = id.split_once(':').map_or(id, |(_, path)| path);
+    path.split('/').next().unwrap_or(path).to_string()
+}
+
+fn percent(completed: usize, total: usize) -> f64 {
+    if total == 0 { 0.0 } else { completed as f64 * 100.0 / total as f64 }
+}
+
+/// Falls back to the world's data version for files written before advancements carried one.
+fn data_version_of(json: &Value, server_path: &str) -> Option<i64> {
+    json.get("DataVersion").and_then(Value::as_i64).or_else(|| {
+        let world_dir = Path::new(server_path).join(worlds::active_world_name(server_path));
+        worlds::read_level_data(&world_dir).ok().and_then(|data| worlds::level_info(&data).data_version)
+    })
+}
+
+/// Parses an advancements file, skipping recipe unlocks which are stored alongside.
+fn parse_progress(json: &Value, vanilla: &[String]) -> Vec<AdvancementProgress> {
+    let Some(entries) = json.as_object() else { return Vec::new(); };
+    let mut list: Vec<AdvancementProgress> = entries.iter()
+        .filter(|(id, value)| value.is_object() && !id.contains(":recipes/"))
+        .map(|(id, value)| {
+            let criteria = value.get("criteria").and_then(Value::as_object);
+            let done = value.get("done").and_then(Value::as_bool).unwrap_or(false);
+            AdvancementProgress {
+                id: id.clone(),
+                category: category_of(id),
+                done,
+                // The timestamp format sorts lexically within one time zone
+                completed_at: criteria.filter(|_| done).and_then(|c| c.values().filter_map(Value::as_str).max().map(String::from)),
+                criteria_done: criteria.map_or(0, |c| c.len()),
+                vanilla: vanilla.contains(id),
+            }
+        })
+        .collect();
+    list.sort_by(|a, b| b.completed_at.cmp(&a.completed_at).then_with(|| a.id.cmp(&b.id)));
+    list
+}
+
+fn read_file(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Completed vanilla advancements, excluding the category roots which are granted immediately.
+fn completed_vanilla<'a>(progress: &'a [AdvancementProgress], vanilla: &'a [String]) -> impl Iterator<Item = &'a AdvancementProgress> {
+    progress.iter().filter(|a| a.done && vanilla.contains(&a.id) && !a.id.ends_with("/root"))
+}
+
+fn counted(vanilla: &[String]) -> impl Iterator<Item = &String> {
+    vanilla.iter().filter(|id| !id.ends_with("/root"))
+}
+
+/// One player's advancements with per-category completion against the vanilla list for their version.
+#[tauri::command]
+pub async fn get_player_advancements(server_path: String, uuid: String, state: State<'_, AppState>) -> Result<PlayerAdvancements, String> {
+    crate::validate_folder_name(&uuid)?;
+    let path = advancements_dir(&server_path).join(format!("{}.json", uuid));
+    if !path.exists() { return Err(format!("No advancements recorded for {}", uuid)); }
+    let json = read_file(&path)?;
+    let data_version = data_version_of(&json, &server_path);
+    let vanilla = vanilla_ids(data_version.unwrap_or(i64::MAX));
+    let advancements = parse_progress(&json, &vanilla);
+
+    let categories = CATEGORIES.iter().map(|category| {
+        let total = counted(&vanilla).filter(|id| category_of(id) == *category).count();
+        let completed = completed_vanilla(&advancements, &vanilla).filter(|a| a.category == *category).count();
+        CategoryProgress { category: category.to_string(), completed, total, percent: percent(completed, total) }
+    }).collect();
+    let missing = counted(&vanilla).filter(|id| !advancements.iter().any(|a| a.done && &a.id == *id)).cloned().collect();
+    let names = profiles::names_for_uuids(&state, &server_path, std::slice::from_ref(&uuid)).await;
+    Ok(PlayerAdvancements {
+        name: names.get(&uuid).cloned().unwrap_or_else(|| uuid.clone()),
+        uuid,
+        data_version,
+        categories,
+        advancements,
+        missing,
+    })
+}
+
+/// Overall vanilla completion for every player in the active world, best first.
+#[tauri::command]
+pub async fn get_advancement_summary(server_path: String, state: State<'_, AppState>) -> Result<Vec<AdvancementSummary>, String> {
+    let Ok(entries) = fs::read_dir(advancements_dir(&server_path)) else { return Ok(Vec::new()); };
+    let mut rows = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") { continue; }
+        let Ok(json) = read_file(&path) else { continue; };
+        let vanilla = vanilla_ids(data_version_of(&json, &server_path).unwrap_or(i64::MAX));
+        let progress = parse_progress(&json, &vanilla);
+        let completed = completed_vanilla(&progress, &vanilla).count();
+        rows.push((path.file_stem().unwrap().to_string_lossy().to_string(), completed, counted(&vanilla).count()));
+    }
+    let uuids: Vec<String> = rows.iter().map(|(uuid, _, _)| uuid.clone()).collect();
+    let names = profiles::names_for_uuids(&state, &server_path, &uuids).await;
+    let mut summary: Vec<AdvancementSummary> = rows.into_iter().map(|(uuid, completed, total)| AdvancementSummary {
+        name: names.get(&uuid).cloned().unwrap_or_else(|| uuid.clone()),
+        uuid,
+        completed,
+        total,
+        percent: percent(completed, total),
+    }).collect();
+    summary.sort_by_key(|s| std::cmp::Reverse(s.completed));
+    Ok(summary)
+}
+
+/// The vanilla advancement ids for a data version, for showing what is still missing.
+#[tauri::command]
+pub fn list_vanilla_advancements(data_version: i64) -> Vec<String> {
+    vanilla_ids(data_version)
+}
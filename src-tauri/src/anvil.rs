@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use tauri::State;
+use walkdir::WalkDir;
+
+use crate::nbt::{self, Compound};
+use crate::{backups, worlds, AppState};
+
+const SECTOR: u64 = 4096;
+/// Heatmaps wider than this many cells are downsampled so huge worlds stay cheap to render.
+const MAX_HEATMAP_CELLS: i32 = 64;
+
+/// Where one chunk lives inside a region file, from the location table at the start of the file.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkLocation {
+    /// Index 0..1024, i.e. `local_x + local_z * 32`.
+    pub index: usize,
+    pub sector_offset: u32,
+    pub sector_count: u32,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CorruptChunk {
+    pub region: String,
+    pub chunk_x: i32,
+    pub chunk_z: i32,
+    pub reason: String,
+}
+
+pub struct RegionScan {
+    pub x: i32,
+    pub z: i32,
+    pub chunks: u32,
+    pub corrupt: Vec<CorruptChunk>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Heatmap {
+    pub min_region_x: i32,
+    pub min_region_z: i32,
+    /// Each cell covers `cell_regions` x `cell_regions` region files (512 blocks per region).
+    pub cell_regions: i32,
+    pub width: i32,
+    pub height: i32,
+    /// Generated chunk counts, row-major (`z` rows of `x` cells).
+    pub cells: Vec<u32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DimensionReport {
+    pub id: String,
+    pub path: String,
+    pub size_mb: f64,
+    pub region_count: usize,
+    pub chunk_count: u64,
+    pub corrupt_chunks: Vec<CorruptChunk>,
+    pub heatmap: Heatmap,
+}
+
+/// Chunks are removed when they match either rule; rules left as `None` are ignored.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TrimOptions {
+    /// Minimum `InhabitedTime` in ticks (20 per second) a chunk needs to be kept.
+    pub min_inhabited_ticks: Option<i64>,
+    /// Chunks further than this many blocks from spawn are removed.
+    pub max_radius_blocks: Option<i64>,
+    /// Dimension ids to trim, e.g. `minecraft:overworld`. Defaults to all.
+    pub dimensions: Option<Vec<String>>,
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct TrimReport {
+    pub dry_run: bool,
+    pub backups: Vec<String>,
+    pub regions_deleted: usize,
+    pub chunks_deleted: u64,
+    pub chunks_kept: u64,
+    /// Chunks whose data could not be read; these are always kept.
+    pub chunks_unreadable: u64,
+    /// Region files left untouched because a chunk to keep could not be read.
+    pub files_skipped: Vec<String>,
+    pub freed_mb: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WorldAnalysis {
+    pub world: String,
+    pub total_size_mb: f64,
+    pub dimensions: Vec<DimensionReport>,
+}
+
+/// Parses `r.<x>.<z>.mca` into region coordinates.
+pub fn region_coords(file_name: &str) -> Option<(i32, i32)> {
+    let mut parts = file_name.strip_prefix("r.")?.strip_suffix(".mca")?.split('.');
+    let x = parts.next()?.parse().ok()?;
+    let z = parts.next()?.parse().ok()?;
+    if parts.next().is_some() { return None; }
+    Some((x, z))
+}
+
+pub fn read_header(file: &mut fs::File) -> Result<Vec<ChunkLocation>, String> {
+    let mut header = vec![0u8; SECTOR as usize];
+    file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    file.read_exact(&mut header).map_err(|_| "Region header is truncated".to_string())?;
+    let mut locations = Vec::new();
+    for index in 0..1024 {
+        let entry = &header[index * 4..index * 4 + 4];
+        let sector_offset = u32::from_be_bytes([0, entry[0], entry[1], entry[2]]);
+        let sector_count = entry[3] as u32;
+        if sector_offset == 0 && sector_count == 0 { continue; }
+        locations.push(ChunkLocation { index, sector_offset, sector_count });
+    }
+    Ok(locations)
+}
+
+/// Validates every chunk entry in a region file without decompressing chunk data.
+pub fn scan_region(path: &Path) -> Result<RegionScan, String> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let (x, z) = region_coords(&name).ok_or_else(|| format!("'{}' is not a region file", name))?;
+    let size = fs::metadata(path).map_err(|e| e.to_string())?.len();
+    let mut scan = RegionScan { x, z, chunks: 0, corrupt: Vec::new() };
+    if size == 0 { return Ok(scan); }
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let locations = match read_header(&mut file) {
+        Ok(l) => l,
+        Err(e) => {
+            scan.corrupt.push(CorruptChunk { region: name, chunk_x: x * 32, chunk_z: z * 32, reason: e });
+            return Ok(scan);
+        }
+    };
+    let total_sectors = size.div_ceil(SECTOR);
+    let mut used = vec![false; total_sectors as usize];
+    for loc in locations {
+        let mut problem = |reason: String| scan.corrupt.push(CorruptChunk {
+            region: name.clone(),
+            chunk_x: x * 32 + (loc.index % 32) as i32,
+            chunk_z: z * 32 + (loc.index / 32) as i32,
+            reason,
+        });
+        let start = loc.sector_offset as u64;
+        let end = start + loc.sector_count as u64;
+        if start < 2 || loc.sector_count == 0 {
+            problem(format!("Invalid location (sector {}, {} sectors)", start, loc.sector_count));
+            continue;
+        }
+        if end > total_sectors {
+            problem("Chunk extends past the end of the file".into());
+            continue;
+        }
+        if used[start as usize..end as usize].iter().any(|u| *u) {
+            problem("Chunk overlaps another chunk".into());
+            continue;
+        }
+        used[start as usize..end as usize].iter_mut().for_each(|u| *u = true);
+        let mut chunk_header = [0u8; 5];
+        if file.seek(SeekFrom::Start(start * SECTOR)).and_then(|_| file.read_exact(&mut chunk_header)).is_err() {
+            problem("Chunk header is unreadable".into());
+            continue;
+        }
+        let length = u32::from_be_bytes([chunk_header[0], chunk_header[1], chunk_header[2], chunk_header[3]]) as u64;
+        let compression = chunk_header[4];
+        let external = compression & 0x80 != 0;
+        if !matches!(compression & 0x7F, 1..=4 | 127) {
+            problem(format!("Unknown compression type {}", compression));
+            continue;
+        }
+        // Oversized chunks are stored in a separate .mcc file and only keep a stub here
+        if !external && (length == 0 || length + 4 > loc.sector_count as u64 * SECTOR) {
+            problem(format!("Chunk length {} does not fit its {} sectors", length, loc.sector_count));
+            continue;
+        }
+        scan.chunks += 1;
+    }
+    Ok(scan)
+}
+
+/// The `c.<x>.<z>.mcc` file next to a region that holds an oversized chunk.
+fn sidecar_path(region_path: &Path, region: (i32, i32), index: usize) -> PathBuf {
+    let (cx, cz) = (region.0 * 32 + (index % 32) as i32, region.1 * 32 + (index / 32) as i32);
+    region_path.with_file_name(format!("c.{}.{}.mcc", cx, cz))
+}
+
+/// Decodes one chunk's NBT. Oversized chunks are read from their `c.<x>.<z>.mcc` sidecar.
+pub fn read_chunk(file: &mut fs::File, region_path: &Path, region: (i32, i32), loc: &ChunkLocation) -> Result<Compound, String> {
+    let mut header = [0u8; 5];
+    file.seek(SeekFrom::Start(loc.sector_offset as u64 * SECTOR)).map_err(|e| e.to_string())?;
+    file.read_exact(&mut header).map_err(|e| e.to_string())?;
+    let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let compression = header[4];
+    let raw = if compression & 0x80 != 0 {
+        fs::read(sidecar_path(region_path, region, loc.index)).map_err(|e| e.to_string())?
+    } else {
+        if length == 0 || length > loc.sector_count as usize * SECTOR as usize { return Err("Invalid chunk length".into()); }
+        let mut data = vec![0u8; length - 1];
+        file.read_exact(&mut data).map_err(|e| e.to_string())?;
+        data
+    };
+    let data = match compression & 0x7F {
+        1 | 2 => nbt::decompress(&raw)?,
+        3 => raw,
+        other => return Err(format!("Unsupported chunk compression {}", other)),
+    };
+    Ok(nbt::from_bytes(&data)?.1)
+}
+
+/// A chunk's stored bytes (length, compression and data), padded to whole sectors. `None` when
+/// the header does not fit its sectors or the data cannot be read.
+fn read_stored_chunk(file: &mut fs::File, loc: &ChunkLocation) -> Option<Vec<u8>> {
+    let mut length = [0u8; 4];
+    file.seek(SeekFrom::Start(loc.sector_offset as u64 * SECTOR)).ok()?;
+    file.read_exact(&mut length).ok()?;
+    let length = u32::from_be_bytes(length) as u64;
+    if length == 0 || length + 4 > loc.sector_count as u64 * SECTOR { return None; }
+    // The last chunk of a file is often not padded, so only read what the header says is there
+    let mut data = vec![0u8; (length + 4) as usize];
+    data[..4].copy_from_slice(&(length as u32).to_be_bytes());
+    file.read_exact(&mut data[4..]).ok()?;
+    data.resize(data.len().div_ceil(SECTOR as usize) * SECTOR as usize, 0);
+    Some(data)
+}
+
+/// Rewrites a region file without the given chunk indices, packing the remaining chunks
+/// together so the space is actually released. Deletes the file when nothing is left, and the
+/// `.mcc` sidecars of removed chunks. Returns the bytes freed, or `None` when a kept chunk could
+/// not be read and the file was left untouched; with `dry_run` only the saving is computed.
+fn remove_chunks(path: &Path, remove: &HashSet<usize>, dry_run: bool) -> Result<Option<u64>, String> {
+    if !path.exists() { return Ok(Some(0)); }
+    let old_size = fs::metadata(path).map_err(|e| e.to_string())?.len();
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let locations = read_header(&mut file)?;
+    let region = path.file_name().and_then(|n| n.to_str()).and_then(region_coords).unwrap_or_default();
+    let sidecars: Vec<(PathBuf, u64)> = locations.iter().filter(|l| remove.contains(&l.index))
+        .filter_map(|l| { let p = sidecar_path(path, region, l.index); fs::metadata(&p).ok().map(|m| (p, m.len())) })
+        .collect();
+    let sidecar_bytes: u64 = sidecars.iter().map(|(_, len)| len).sum();
+    let kept: Vec<ChunkLocation> = locations.into_iter().filter(|l| !remove.contains(&l.index)).collect();
+    let estimate = 2 * SECTOR + kept.iter().map(|l| l.sector_count as u64 * SECTOR).sum::<u64>();
+    if dry_run {
+        let region_bytes = if kept.is_empty() { old_size } else { old_size.saturating_sub(estimate) };
+        return Ok(Some(region_bytes + sidecar_bytes));
+    }
+
+    let mut new_size = 0;
+    if kept.is_empty() {
+        drop(file);
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    } else if estimate < old_size {
+        let mut timestamps = vec![0u8; SECTOR as usize];
+        file.seek(SeekFrom::Start(SECTOR)).map_err(|e| e.to_string())?;
+        file.read_exact(&mut timestamps).map_err(|e| e.to_string())?;
+        let mut locations = vec![0u8; SECTOR as usize];
+        let mut new_timestamps = vec![0u8; SECTOR as usize];
+        let mut body = Vec::new();
+        let mut next_sector = 2u32;
+        for loc in &kept {
+            let Some(data) = read_stored_chunk(&mut file, loc) else { return Ok(None); };
+            let sectors = (data.len() as u64 / SECTOR) as u32;
+            body.extend_from_slice(&data);
+            let i = loc.index * 4;
+            locations[i..i + 3].copy_from_slice(&next_sector.to_be_bytes()[1..]);
+            locations[i + 3] = sectors as u8;
+            new_timestamps[i..i + 4].copy_from_slice(&timestamps[i..i + 4]);
+            next_sector += sectors;
+        }
+        drop(file);
+        let tmp = path.with_extension("mca.tmp");
+        let mut out = fs::File::create(&tmp).map_err(|e| e.to_string())?;
+        out.write_all(&locations).and_then(|_| out.write_all(&new_timestamps)).and_then(|_| out.write_all(&body)).map_err(|e| e.to_string())?;
+        drop(out);
+        fs::rename(&tmp, path).map_err(|e| e.to_string())?;
+        new_size = 2 * SECTOR + body.len() as u64;
+    } else {
+        new_size = old_size;
+    }
+    for (sidecar, _) in &sidecars { fs::remove_file(sidecar).map_err(|e| e.to_string())?; }
+    Ok(Some(old_size.saturating_sub(new_size) + sidecar_bytes))
+}
+
+fn heatmap(regions: &[RegionScan]) -> Heatmap {
+    if regions.is_empty() {
+        return Heatmap { min_region_x: 0, min_region_z: 0, cell_regions: 1, width: 0, height: 0, cells: Vec::new() };
+    }
+    let min_x = regions.iter().map(|r| r.x).min().unwrap();
+    let max_x = regions.iter().map(|r| r.x).max().unwrap();
+    let min_z = regions.iter().map(|r| r.z).min().unwrap();
+    let max_z = regions.iter().map(|r| r.z).max().unwrap();
+    let span = (max_x - min_x + 1).max(max_z - min_z + 1);
+    let cell_regions = (span + MAX_HEATMAP_CELLS - 1) / MAX_HEATMAP_CELLS;
+    let width = (max_x - min_x) / cell_regions + 1;
+    let height = (max_z - min_z) / cell_regions + 1;
+    let mut cells = vec![0u32; (width * height) as usize];
+    for r in regions {
+        let cx = (r.x - min_x) / cell_regions;
+        let cz = (r.z - min_z) / cell_regions;
+        cells[(cz * width + cx) as usize] += r.chunks;
+    }
+    Heatmap { min_region_x: min_x, min_region_z: min_z, cell_regions, width, height, cells }
+}
+
+/// Every dimension folder of a world as `(dimension id, folder containing region/entities/poi)`.
+/// Bukkit's split `<world>_nether` / `<world>_the_end` folders are included when present.
+pub fn dimension_folders(server_path: &str, world_name: &str) -> Vec<(String, PathBuf)> {
+    let root = Path::new(server_path);
+    let world_dir = root.join(world_name);
+    let mut dims = vec![("minecraft:overworld".to_string(), world_dir.clone())];
+    for (id, dim, suffix) in [("minecraft:the_nether", "DIM-1", "_nether"), ("minecraft:the_end", "DIM1", "_the_end")] {
+        let split = root.join(format!("{}{}", world_name, suffix)).join(dim);
+        let path = if split.is_dir() { split } else { world_dir.join(dim) };
+        if path.is_dir() { dims.push((id.to_string(), path)); }
+    }
+    // Datapack dimensions live at dimensions/<namespace>/<path...>/region
+    let custom_root = world_dir.join("dimensions");
+    for entry in WalkDir::new(&custom_root).min_depth(2).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_dir() || !entry.path().join("region").is_dir() { continue; }
+        let Ok(rel) = entry.path().strip_prefix(&custom_root) else { continue; };
+        let mut parts = rel.iter().map(|p| p.to_string_lossy().to_string());
+        let namespace = parts.next().unwrap_or_default();
+        if namespace == "minecraft" && matches!(rel.to_string_lossy().as_ref(), "minecraft/overworld" | "minecraft/the_nether" | "minecraft/the_end") { continue; }
+        let id = format!("{}:{}", namespace, parts.collect::<Vec<_>>().join("/"));
+        dims.push((id, entry.path().to_path_buf()));
+    }
+    dims
+}
+
+/// Region files of one dimension folder, ordered by file name.
+pub fn region_files(dimension_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dimension_dir.join("region")) else { return Vec::new(); };
+    let mut files: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).and_then(region_coords).is_some())
+        .collect();
+    files.sort();
+    files
+}
+
+fn dimension_size(dimension_dir: &Path) -> u64 {
+    ["region", "entities", "poi", "data"].iter()
+        .flat_map(|sub| WalkDir::new(dimension_dir.join(sub)).into_iter().filter_map(|e| e.ok()))
+        .filter_map(|e| e.metadata().ok()).filter(|m| m.is_file()).map(|m| m.len()).sum()
+}
+
+#[tauri::command]
+pub async fn analyze_world(server_path: String, world_name: String) -> Result<WorldAnalysis, String> {
+    crate::validate_folder_name(&world_name)?;
+    if !Path::new(&server_path).join(&world_name).join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+    let mut dimensions = Vec::new();
+    let mut total = 0;
+    for (id, dir) in dimension_folders(&server_path, &world_name) {
+        let regions: Vec<RegionScan> = region_files(&dir).iter().map(|p| scan_region(p)).collect::<Result<_, _>>()?;
+        let size = dimension_size(&dir);
+        total += size;
+        dimensions.push(DimensionReport {
+            id,
+            path: dir.to_string_lossy().to_string(),
+            size_mb: (size as f64) / 1024.0 / 1024.0,
+            region_count: regions.len(),
+            chunk_count: regions.iter().map(|r| r.chunks as u64).sum(),
+            corrupt_chunks: regions.iter().flat_map(|r| r.corrupt.iter().cloned()).collect(),
+            heatmap: heatmap(&regions),
+        });
+    }
+    Ok(WorldAnalysis { world: world_name, total_size_mb: (total as f64) / 1024.0 / 1024.0, dimensions })
+}
+
+fn inhabited_time(chunk: &Compound) -> i64 {
+    // Pre-1.18 chunks nest everything under `Level`
+    chunk.get("InhabitedTime").or_else(|| chunk.path("Level.InhabitedTime")).and_then(|t| t.as_i64()).unwrap_or(0)
+}
+
+/// Removes rarely visited or far-away chunks from a world's region, entity and POI files.
+/// A real run backs up every affected world folder first; `dry_run` only reports the savings.
+#[tauri::command]
+pub async fn trim_world(app: tauri::AppHandle, server_path: String, world_name: String, options: TrimOptions, state: State<'_, AppState>) -> Result<TrimReport, String> {
+    crate::validate_folder_name(&world_name)?;
+    let world_dir = Path::new(&server_path).join(&world_name);
+    if !world_dir.join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+    if options.min_inhabited_ticks.is_none() && options.max_radius_blocks.is_none() {
+        return Err("Set an inhabited time threshold or a radius to trim by".into());
+    }
+    if !options.dry_run {
+        crate::ensure_server_stopped(&state, &server_path)?;
+    }
+    let spawn = worlds::level_info(&worlds::read_level_data(&world_dir)?).spawn.unwrap_or([0, 0, 0]);
+    let mut report = TrimReport { dry_run: options.dry_run, ..Default::default() };
+    if !options.dry_run {
+        for folder in worlds::world_folders(&server_path, &world_name) {
+            if Path::new(&server_path).join(&folder).join("level.dat").exists() {
+                report.backups.push(backups::run_backup(&app, &state, &server_path, &folder)?);
+            }
+        }
+    }
+
+    let mut freed = 0u64;
+    for (id, dir) in dimension_folders(&server_path, &world_name) {
+        if options.dimensions.as_ref().is_some_and(|dims| !dims.contains(&id)) { continue; }
+        // Distances are measured from spawn in the overworld, scaled for the nether, and from 0,0 elsewhere
+        let (center_x, center_z) = match id.as_str() {
+            "minecraft:overworld" => (spawn[0], spawn[2]),
+            "minecraft:the_nether" => (spawn[0] / 8, spawn[2] / 8),
+            _ => (0, 0),
+        };
+        for region_path in region_files(&dir) {
+            let name = region_path.file_name().unwrap().to_string_lossy().to_string();
+            let (rx, rz) = region_coords(&name).unwrap();
+            let mut file = fs::File::open(&region_path).map_err(|e| e.to_string())?;
+            let Ok(locations) = read_header(&mut file) else { continue; };
+            let mut remove = HashSet::new();
+            for loc in &locations {
+                let block_x = (rx * 32 + (loc.index % 32) as i32) as i64 * 16 + 8;
+                let block_z = (rz * 32 + (loc.index / 32) as i32) as i64 * 16 + 8;
+                let outside = options.max_radius_blocks.is_some_and(|r| {
+                    let (dx, dz) = (block_x - center_x, block_z - center_z);
+                    dx * dx + dz * dz > r * r
+                });
+                let unvisited = match options.min_inhabited_ticks {
+                    Some(min) if !outside => match read_chunk(&mut file, &region_path, (rx, rz), loc) {
+                        Ok(chunk) => inhabited_time(&chunk) < min,
+                        Err(_) => { report.chunks_unreadable += 1; false }
+                    },
+                    _ => false,
+                };
+                if outside || unvisited { remove.insert(loc.index); }
+            }
+            drop(file);
+            report.chunks_deleted += remove.len() as u64;
+            report.chunks_kept += (locations.len() - remove.len()) as u64;
+            if remove.is_empty() { continue; }
+            if remove.len() == locations.len() { report.regions_deleted += 1; }
+            // Region goes first: if it has to be left alone, its entities and POI stay with it
+            for sub in ["region", "entities", "poi"] {
+                match remove_chunks(&dir.join(sub).join(&name), &remove, options.dry_run)? {
+                    Some(bytes) => freed += bytes,
+                    None => {
+                        report.files_skipped.push(format!("{}/{}", sub, name));
+                        if sub == "region" { break; }
+                    }
+                }
+            }
+        }
+    }
+    report.freed_mb = (freed as f64) / 1024.0 / 1024.0;
+    Ok(report)
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{backup_store, backups, find_orphaned_java_process, is_managed_server, AppState};
+
+const TICK: Duration = Duration::from_secs(30);
+
+/// Automatic backup settings stored per server alongside its tunnel config.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct BackupSchedule {
+    pub enabled: bool,
+    pub worlds: Vec<String>,
+    pub interval_minutes: Option<u64>,
+    /// Standard 5-field cron expression (a leading seconds field is also accepted).
+    pub cron: Option<String>,
+    /// Also back up when the server is stopped and nobody played since the last backup. Off by
+    /// default, so idle servers are not backed up on every run.
+    #[serde(default)]
+    pub backup_when_idle: bool,
+    /// Write to the deduplicated snapshot store instead of creating zip archives.
+    #[serde(default)]
+    pub incremental: bool,
+    #[serde(default)]
+    pub on_stop: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BackupEvent {
+    pub server_path: String,
+    pub world: String,
+    pub trigger: String,
+    pub success: bool,
+    pub result: String,
+    pub timestamp: String,
+}
+
+fn parse_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let fields = expression.split_whitespace().count();
+    let expression = if fields == 5 { format!("0 {}", expression) } else { expression.to_string() };
+    cron::Schedule::from_str(&expression).map_err(|e| format!("Invalid cron expression: {}", e))
+}
+
+fn next_run(schedule: &BackupSchedule, last_run: &DateTime<Local>) -> Option<DateTime<Local>> {
+    if let Some(expression) = schedule.cron.as_deref().filter(|c| !c.trim().is_empty()) {
+        return parse_cron(expression).ok()?.after(last_run).next();
+    }
+    let minutes = schedule.interval_minutes.filter(|m| *m > 0)?;
+    Some(*last_run + chrono::Duration::minutes(minutes as i64))
+}
+
+fn server_active(state: &AppState, server_path: &str) -> bool {
+    is_managed_server(state, server_path)
+        || state.players_seen.lock().unwrap().contains(server_path)
+        || find_orphaned_java_process(server_path).is_some()
+}
+
+fn append_log(server_path: &str, event: &BackupEvent) {
+    let dir = backups::backup_dir(server_path);
+    if std::fs::create_dir_all(&dir).is_err() { return; }
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(dir.join("backup.log")) {
+        let status = if event.success { "OK" } else { "FAILED" };
+        let _ = writeln!(file, "{} [{}] {} {}: {}", event.timestamp, event.trigger, status, event.world, event.result);
+    }
+}
+
+/// Backs up every world in the schedule, reporting each result as a `backup-result` event,
+/// a console line and an entry in `roam_backups/backup.log`.
+pub(crate) fn run_scheduled(app: &AppHandle, server_path: &str, schedule: &BackupSchedule, trigger: &str) {
+    let state = app.state::<AppState>();
+    let mut all_ok = true;
+    for world in &schedule.worlds {
+        let result = if schedule.incremental {
+            backup_store::create_snapshot(&state, server_path, world).map(|r| r.id)
+        } else {
+            backups::run_backup(app, &state, server_path, world)
+        };
+        all_ok &= result.is_ok();
+        let event = BackupEvent {
+            server_path: server_path.to_string(),
+            world: world.clone(),
+            trigger: trigger.to_string(),
+            success: result.is_ok(),
+            result: result.unwrap_or_else(|e| e),
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+        let line = if event.success { format!("[System] {} backup of {} finished: {}", trigger, world, event.result) }
+            else { format!("[System] {} backup of {} failed: {}", trigger, world, event.result) };
+        app.emit("server-log", line).unwrap();
+        append_log(server_path, &event);
+        app.emit("backup-result", event).unwrap();
+    }
+    if all_ok { state.players_seen.lock().unwrap().remove(server_path); }
+}
+
+/// Starts the background thread that checks the active server's schedule every 30 seconds.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        // Counted from when a server is first seen, so selecting one does not fire a backup immediately
+        let mut last_runs: HashMap<String, DateTime<Local>> = HashMap::new();
+        loop {
+            std::thread::sleep(TICK);
+            let state = app.state::<AppState>();
+            let config = state.config.lock().unwrap().clone();
+            let Some(config) = config else { continue; };
+            let Some(schedule) = config.backup_schedule.filter(|s| s.enabled && !s.worlds.is_empty()) else { continue; };
+            let now = Local::now();
+            let last_run = *last_runs.entry(config.path.clone()).or_insert(now);
+            if next_run(&schedule, &last_run).is_none_or(|due| due > now) { continue; }
+            last_runs.insert(config.path.clone(), now);
+            if !schedule.backup_when_idle && !server_active(&state, &config.path) { continue; }
+            run_scheduled(&app, &config.path, &schedule, "Scheduled");
+        }
+    });
+}
+
+/// Lists the next few run times so the UI can validate and preview a schedule.
+#[tauri::command]
+pub fn preview_backup_schedule(schedule: BackupSchedule) -> Result<Vec<String>, String> {
+    if let Some(expression) = schedule.cron.as_deref().filter(|c| !c.trim().is_empty()) { parse_cron(expression)?; }
+    let mut runs = Vec::new();
+    let mut last = Local::now();
+    while runs.len() < 5 {
+        let Some(next) = next_run(&schedule, &last) else { break; };
+        runs.push(next.format("%Y-%m-%d %H:%M").to_string());
+        last = next;
+    }
+    Ok(runs)
+}
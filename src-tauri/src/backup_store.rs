@@ -0,0 +1,325 @@
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tauri::{Emitter, Manager, State};
+use walkdir::WalkDir;
+
+use crate::backups::{backup_dir, staging_dir, swap_in_world, with_saving_paused};
+use crate::AppState;
+
+/// Region files are split on 64 KiB boundaries (16 sectors) so an edited chunk only
+/// invalidates the blocks it touches; everything else is split into 4 MiB blocks.
+const REGION_BLOCK: usize = 64 * 1024;
+const FILE_BLOCK: usize = 4 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone)]
+struct ManifestFile {
+    path: String,
+    size: u64,
+    chunks: Vec<String>,
+}
+
+/// One snapshot of a world: the list of its files and the content hashes that rebuild them.
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    id: String,
+    world: String,
+    created: String,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub world: String,
+    pub timestamp: String,
+    pub file_count: usize,
+    pub size_mb: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SnapshotResult {
+    pub id: String,
+    pub new_chunks: usize,
+    pub reused_chunks: usize,
+    pub stored_mb: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PruneResult {
+    pub removed_chunks: usize,
+    pub freed_mb: f64,
+    /// Manifests that could not be read; chunks only they reference are removed too.
+    pub skipped_snapshots: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct VerifyReport {
+    pub snapshots_checked: usize,
+    pub chunks_checked: usize,
+    pub missing: Vec<String>,
+    pub corrupt: Vec<String>,
+    pub skipped_snapshots: Vec<String>,
+}
+
+/// One lock per server, held by snapshots and pruning so a prune never deletes objects a running
+/// snapshot has written but not yet referenced from its manifest.
+static STORE_LOCKS: Mutex<BTreeMap<String, Arc<Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+fn store_lock(server_path: &str) -> Arc<Mutex<()>> {
+    STORE_LOCKS.lock().unwrap().entry(server_path.to_string()).or_default().clone()
+}
+
+fn store_dir(server_path: &str) -> PathBuf {
+    backup_dir(server_path).join("store")
+}
+
+fn object_path(server_path: &str, hash: &str) -> PathBuf {
+    store_dir(server_path).join("objects").join(&hash[..2]).join(hash)
+}
+
+fn snapshot_path(server_path: &str, id: &str) -> PathBuf {
+    store_dir(server_path).join("snapshots").join(format!("{}.json", id))
+}
+
+fn hash_bytes(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Writes a compressed object unless one with the same hash already exists. Returns the bytes written.
+fn store_object(server_path: &str, data: &[u8]) -> Result<(String, Option<u64>), String> {
+    let hash = hash_bytes(data);
+    let path = object_path(server_path, &hash);
+    if path.exists() { return Ok((hash, None)); }
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+    // Write then rename so an interrupted backup never leaves a truncated object behind
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, &compressed).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())?;
+    Ok((hash, Some(compressed.len() as u64)))
+}
+
+fn is_hash(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn read_object(server_path: &str, hash: &str) -> Result<Vec<u8>, String> {
+    if !is_hash(hash) { return Err(format!("Invalid chunk id '{}'", hash)); }
+    let file = fs::File::open(object_path(server_path, hash)).map_err(|e| format!("Missing chunk {}: {}", hash, e))?;
+    let mut data = Vec::new();
+    ZlibDecoder::new(file).read_to_end(&mut data).map_err(|e| format!("Corrupt chunk {}: {}", hash, e))?;
+    Ok(data)
+}
+
+fn load_manifest(path: &Path) -> Result<Manifest, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e))
+}
+
+/// Every readable manifest, newest first, plus an error line for each one that could not be read
+/// so a single damaged file does not block the rest.
+fn load_manifests(server_path: &str) -> Result<(Vec<Manifest>, Vec<String>), String> {
+    let dir = store_dir(server_path).join("snapshots");
+    if !dir.exists() { return Ok((Vec::new(), Vec::new())); }
+    let (mut manifests, mut skipped) = (Vec::new(), Vec::new());
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|ext| ext != "json") { continue; }
+        match load_manifest(&path) {
+            Ok(manifest) => manifests.push(manifest),
+            Err(e) => skipped.push(e),
+        }
+    }
+    manifests.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok((manifests, skipped))
+}
+
+fn validate_snapshot_id(id: &str) -> Result<(), String> {
+    crate::validate_folder_name(id)
+}
+
+/// Chunks every file of the world into the store and writes a manifest for it.
+pub(crate) fn create_snapshot(state: &AppState, server_path: &str, world_name: &str) -> Result<SnapshotResult, String> {
+    crate::validate_folder_name(world_name)?;
+    let world_dir = Path::new(server_path).join(world_name);
+    if !world_dir.join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+    let lock = store_lock(server_path);
+    let _guard = lock.lock().unwrap();
+    let now = chrono::Local::now();
+    // Ids only have one-second resolution, so number any further snapshots within that second
+    let base = format!("{}_{}", world_name, now.format("%Y-%m-%d_%H-%M-%S"));
+    let id = (1..).map(|seq| if seq == 1 { base.clone() } else { format!("{}-{}", base, seq) })
+        .find(|id| !snapshot_path(server_path, id).exists()).unwrap();
+    let mut manifest = Manifest { id: id.clone(), world: world_name.to_string(), created: now.format("%Y-%m-%d %H:%M:%S").to_string(), files: Vec::new() };
+    let (mut new_chunks, mut reused_chunks, mut stored) = (0, 0, 0u64);
+
+    with_saving_paused(state, server_path, || {
+        for entry in WalkDir::new(&world_dir) {
+            let entry = entry.map_err(|e| e.to_string())?;
+            if !entry.file_type().is_file() || entry.file_name() == "session.lock" { continue; }
+            let rel = entry.path().strip_prefix(&world_dir).map_err(|e| e.to_string())?.to_string_lossy().replace('\\', "/");
+            let block = if rel.ends_with(".mca") { REGION_BLOCK } else { FILE_BLOCK };
+            let mut file = fs::File::open(entry.path()).map_err(|e| e.to_string())?;
+            let mut buffer = vec![0u8; block];
+            let mut record = ManifestFile { path: rel, size: 0, chunks: Vec::new() };
+            loop {
+                let mut filled = 0;
+                while filled < block {
+                    let read = file.read(&mut buffer[filled..]).map_err(|e| e.to_string())?;
+                    if read == 0 { break; }
+                    filled += read;
+                }
+                if filled == 0 { break; }
+                let (hash, written) = store_object(server_path, &buffer[..filled])?;
+                match written {
+                    Some(bytes) => { new_chunks += 1; stored += bytes; }
+                    None => reused_chunks += 1,
+                }
+                record.size += filled as u64;
+                record.chunks.push(hash);
+                if filled < block { break; }
+            }
+            manifest.files.push(record);
+        }
+        Ok(())
+    })?;
+
+    let path = snapshot_path(server_path, &id);
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string(&manifest).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())?;
+    Ok(SnapshotResult { id, new_chunks, reused_chunks, stored_mb: (stored as f64) / 1024.0 / 1024.0 })
+}
+
+#[tauri::command]
+pub async fn snapshot_world(app: tauri::AppHandle, server_path: String, world_name: String) -> Result<SnapshotResult, String> {
+    tauri::async_runtime::spawn_blocking(move || create_snapshot(&app.state::<AppState>(), &server_path, &world_name))
+        .await.map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn list_snapshots(app: tauri::AppHandle, server_path: String) -> Result<Vec<SnapshotInfo>, String> {
+    let (manifests, skipped) = load_manifests(&server_path)?;
+    for e in skipped { let _ = app.emit("server-log", format!("[System] Skipped snapshot: {}", e)); }
+    Ok(manifests.into_iter().map(|m| SnapshotInfo {
+        size_mb: (m.files.iter().map(|f| f.size).sum::<u64>() as f64) / 1024.0 / 1024.0,
+        file_count: m.files.len(),
+        id: m.id,
+        world: m.world,
+        timestamp: m.created,
+    }).collect())
+}
+
+#[tauri::command]
+pub async fn delete_snapshot(server_path: String, snapshot_id: String) -> Result<(), String> {
+    validate_snapshot_id(&snapshot_id)?;
+    fs::remove_file(snapshot_path(&server_path, &snapshot_id)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn restore_snapshot(server_path: String, snapshot_id: String, target_world_name: String, state: State<'_, AppState>) -> Result<String, String> {
+    crate::ensure_server_stopped(&state, &server_path)?;
+    validate_snapshot_id(&snapshot_id)?;
+    crate::validate_folder_name(&target_world_name)?;
+    tauri::async_runtime::spawn_blocking(move || rebuild_snapshot(&server_path, &snapshot_id, &target_world_name))
+        .await.map_err(|e| e.to_string())?
+}
+
+/// Rebuilds a snapshot into a staging folder and swaps it in as `target_world_name`.
+fn rebuild_snapshot(server_path: &str, snapshot_id: &str, target_world_name: &str) -> Result<String, String> {
+    let lock = store_lock(server_path);
+    let _guard = lock.lock().unwrap();
+    let manifest = load_manifest(&snapshot_path(server_path, snapshot_id))?;
+    let staging = staging_dir(server_path);
+    let rebuild = || -> Result<(), String> {
+        // Created up front so a world without files still has a folder to swap in
+        fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+        for file in &manifest.files {
+            let out_path = staging.join(&file.path);
+            if !out_path.starts_with(&staging) || file.path.split('/').any(|c| c == "..") {
+                return Err(format!("Snapshot entry '{}' escapes the world folder", file.path));
+            }
+            if let Some(parent) = out_path.parent() { fs::create_dir_all(parent).map_err(|e| e.to_string())?; }
+            let mut out = fs::File::create(&out_path).map_err(|e| e.to_string())?;
+            for hash in &file.chunks { out.write_all(&read_object(server_path, hash)?).map_err(|e| e.to_string())?; }
+        }
+        Ok(())
+    };
+    if let Err(e) = rebuild() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    let snapshot = swap_in_world(server_path, &staging, target_world_name)?;
+    Ok(snapshot.map(|p| p.to_string_lossy().to_string()).unwrap_or_default())
+}
+
+/// Deletes stored chunks that no snapshot manifest references any more. Objects still being
+/// written (`.tmp`) or written after the prune started are left alone.
+#[tauri::command]
+pub async fn prune_backup_store(server_path: String) -> Result<PruneResult, String> {
+    tauri::async_runtime::spawn_blocking(move || prune(&server_path)).await.map_err(|e| e.to_string())?
+}
+
+fn prune(server_path: &str) -> Result<PruneResult, String> {
+    let started = SystemTime::now();
+    let lock = store_lock(server_path);
+    let _guard = lock.lock().unwrap();
+    let (manifests, skipped_snapshots) = load_manifests(server_path)?;
+    let referenced: HashSet<String> = manifests.into_iter()
+        .flat_map(|m| m.files.into_iter().flat_map(|f| f.chunks))
+        .collect();
+    let objects_dir = store_dir(server_path).join("objects");
+    let mut result = PruneResult { removed_chunks: 0, freed_mb: 0.0, skipped_snapshots };
+    if !objects_dir.exists() { return Ok(result); }
+    let mut freed = 0u64;
+    for entry in WalkDir::new(&objects_dir).min_depth(2).max_depth(2) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name().to_string_lossy();
+        if !is_hash(&name) || referenced.contains(name.as_ref()) { continue; }
+        let Ok(metadata) = entry.metadata() else { continue; };
+        if metadata.modified().is_ok_and(|m| m >= started) { continue; }
+        freed += metadata.len();
+        fs::remove_file(entry.path()).map_err(|e| e.to_string())?;
+        result.removed_chunks += 1;
+    }
+    result.freed_mb = (freed as f64) / 1024.0 / 1024.0;
+    Ok(result)
+}
+
+/// Re-hashes every chunk referenced by a snapshot and reports missing or damaged ones.
+#[tauri::command]
+pub async fn verify_backup_store(server_path: String) -> Result<VerifyReport, String> {
+    let (manifests, skipped_snapshots) = load_manifests(&server_path)?;
+    let mut report = VerifyReport { snapshots_checked: manifests.len(), chunks_checked: 0, missing: Vec::new(), corrupt: Vec::new(), skipped_snapshots };
+    let mut seen = HashSet::new();
+    for hash in manifests.iter().flat_map(|m| m.files.iter().flat_map(|f| f.chunks.iter())) {
+        if !seen.insert(hash) { continue; }
+        report.chunks_checked += 1;
+        if !is_hash(hash) {
+            report.corrupt.push(hash.clone());
+            continue;
+        }
+        if !object_path(&server_path, hash).exists() {
+            report.missing.push(hash.clone());
+            continue;
+        }
+        match read_object(&server_path, hash) {
+            Ok(data) if hash_bytes(&data) == *hash => {}
+            _ => report.corrupt.push(hash.clone()),
+        }
+    }
+    Ok(report)
+}
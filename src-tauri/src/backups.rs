@@ -0,0 +1,426 @@
+use chrono::{Datelike, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use tauri::{Emitter, Manager, State};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+
+use crate::rcon::RconClient;
+use crate::{find_orphaned_java_process, AppState};
+
+const SAVE_TIMEOUT: Duration = Duration::from_secs(60);
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+const CATALOG_FILE: &str = "catalog.json";
+
+/// One pause at a time per server: the save waiter has a single slot, and a second caller's
+/// `save-on` must not re-enable saving while the first is still copying.
+static PAUSE_LOCKS: Mutex<BTreeMap<String, Arc<Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+/// Per-server pruning rules applied to each world's archives after a backup.
+/// A backup survives if any count/age rule keeps it; with no such rules set, all are kept.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RetentionPolicy {
+    pub keep_last: Option<usize>,
+    pub keep_daily_days: Option<u32>,
+    pub keep_weekly_weeks: Option<u32>,
+    pub max_total_mb: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub world: String,
+    pub timestamp: String,
+    pub size_mb: f64,
+    pub checksum: String,
+}
+
+/// Cached SHA-256 per archive so listing does not rehash multi-GB zips every time.
+#[derive(Serialize, Deserialize, Default)]
+struct CatalogEntry {
+    size: u64,
+    sha256: String,
+}
+
+struct BackupFile {
+    file_name: String,
+    world: String,
+    created: NaiveDateTime,
+    /// Orders backups made within the same second; `1` for the first.
+    seq: u32,
+    size: u64,
+}
+
+/// How the running server is told to pause and flush its autosaves.
+enum SaveControl {
+    Stdin,
+    Rcon(RconClient),
+}
+
+impl SaveControl {
+    /// Returns `None` when the server is stopped and the world can be copied as-is.
+    fn acquire(state: &AppState, server_path: &str) -> Result<Option<Self>, String> {
+        if crate::is_managed_server(state, server_path) { return Ok(Some(SaveControl::Stdin)); }
+        if let Some(pid) = find_orphaned_java_process(server_path) {
+            return match RconClient::from_properties(server_path) {
+                Some(client) => Ok(Some(SaveControl::Rcon(client?))),
+                None => Err(format!("Server is running outside the manager (PID {}) and RCON is not enabled. Stop it or enable RCON to back up safely.", pid)),
+            };
+        }
+        Ok(None)
+    }
+
+    fn command(&mut self, state: &AppState, command: &str) -> Result<(), String> {
+        match self {
+            SaveControl::Stdin => crate::write_to_stdin(state, command),
+            SaveControl::Rcon(client) => client.command(command).map(|_| ()),
+        }
+    }
+
+    /// Disables autosave and blocks until the server reports "Saved the game".
+    fn suspend(&mut self, state: &AppState) -> Result<(), String> {
+        self.command(state, "save-off")?;
+        match self {
+            SaveControl::Stdin => {
+                let (tx, rx) = mpsc::channel();
+                *state.save_waiter.lock().unwrap() = Some(tx);
+                crate::write_to_stdin(state, "save-all flush")?;
+                let result = rx.recv_timeout(SAVE_TIMEOUT);
+                state.save_waiter.lock().unwrap().take();
+                result.map_err(|_| "Timed out waiting for the server to save the world".to_string())
+            }
+            // RCON replies only once the command has finished, so the flush is already done here.
+            SaveControl::Rcon(client) => client.command("save-all flush").map(|_| ()),
+        }
+    }
+}
+
+/// A folder to add to an archive under `prefix`, leaving out the top-level entries named in `skip`.
+pub(crate) struct ZipSource<'a> {
+    pub dir: PathBuf,
+    pub prefix: String,
+    pub skip: &'a [&'a str],
+}
+
+pub(crate) fn write_zip(sources: &[ZipSource], zip_path: &Path, on_file: &mut dyn FnMut(u64)) -> Result<(), String> {
+    let file = fs::File::create(zip_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated).unix_permissions(0o755);
+    let mut buffer = Vec::new();
+    for source in sources {
+        let skipped = |e: &walkdir::DirEntry| e.depth() == 1 && source.skip.iter().any(|s| e.file_name() == *s);
+        for entry in WalkDir::new(&source.dir).into_iter().filter_entry(|e| !skipped(e)) {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path();
+            let name = path.strip_prefix(&source.dir).map_err(|e| e.to_string())?;
+            let zip_name = format!("{}{}", source.prefix, name.to_string_lossy().replace('\\', "/"));
+            if path.is_file() {
+                // The lock is held open by the server and is meaningless inside an archive
+                if name.as_os_str() == "session.lock" { continue; }
+                zip.start_file(zip_name, options).map_err(|e| e.to_string())?;
+                let mut f = fs::File::open(path).map_err(|e| e.to_string())?;
+                f.read_to_end(&mut buffer).map_err(|e| e.to_string())?;
+                zip.write_all(&buffer).map_err(|e| e.to_string())?;
+                on_file(buffer.len() as u64);
+                buffer.clear();
+            } else if !zip_name.is_empty() {
+                zip.add_directory(zip_name, options).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn write_world_zip(world_dir: &Path, zip_path: &Path) -> Result<(), String> {
+    write_zip(&[ZipSource { dir: world_dir.to_path_buf(), prefix: String::new(), skip: &[] }], zip_path, &mut |_| {})
+}
+
+/// Runs `f` with autosave disabled and the world flushed when the server is live, re-enabling it afterwards.
+pub(crate) fn with_saving_paused<T>(state: &AppState, server_path: &str, f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let lock = PAUSE_LOCKS.lock().unwrap().entry(server_path.to_string()).or_default().clone();
+    let _guard = lock.lock().unwrap();
+    let Some(mut control) = SaveControl::acquire(state, server_path)? else { return f(); };
+    let result = control.suspend(state).and_then(|_| f());
+    let resumed = control.command(state, "save-on");
+    let value = result?;
+    resumed?;
+    Ok(value)
+}
+
+/// Archives a world into `roam_backups/`, pausing autosave first if the server is live.
+pub(crate) fn create_backup(state: &AppState, server_path: &str, world_name: &str) -> Result<String, String> {
+    let world_dir = Path::new(server_path).join(world_name);
+    if !world_dir.join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+    let backup_dir = backup_dir(server_path);
+    if !backup_dir.exists() { fs::create_dir_all(&backup_dir).map_err(|e| e.to_string())?; }
+    let timestamp = chrono::Local::now().format(TIMESTAMP_FORMAT).to_string();
+    // Names only have one-second resolution, so number any further backups within that second
+    let backup_filename = (1..).map(|seq| match seq {
+        1 => format!("{}_{}.zip", world_name, timestamp),
+        _ => format!("{}_{}-{}.zip", world_name, timestamp, seq),
+    }).find(|name| !backup_dir.join(name).exists()).unwrap();
+    let backup_path = backup_dir.join(&backup_filename);
+
+    if let Err(e) = with_saving_paused(state, server_path, || write_world_zip(&world_dir, &backup_path)) {
+        let _ = fs::remove_file(&backup_path);
+        return Err(e);
+    }
+    Ok(backup_filename)
+}
+
+/// Creates a backup and then applies the server's retention policy, logging anything it prunes.
+pub(crate) fn run_backup(app: &tauri::AppHandle, state: &AppState, server_path: &str, world_name: &str) -> Result<String, String> {
+    let backup_filename = create_backup(state, server_path, world_name)?;
+    let policy = state.config.lock().unwrap().as_ref().filter(|c| c.path == server_path).and_then(|c| c.retention.clone());
+    if let Some(policy) = policy {
+        for removed in enforce_retention(server_path, world_name, &policy)? {
+            app.emit("server-log", format!("[System] Retention removed backup: {}", removed)).unwrap();
+        }
+    }
+    Ok(backup_filename)
+}
+
+pub(crate) fn backup_dir(server_path: &str) -> PathBuf {
+    Path::new(server_path).join("roam_backups")
+}
+
+/// Splits `<world>_<YYYY-MM-DD_HH-MM-SS>.zip` (or `..._HH-MM-SS-<n>.zip` for the n-th backup in
+/// that second) back into its world name, timestamp and sequence number.
+fn parse_backup_name(file_name: &str) -> Option<(String, NaiveDateTime, u32)> {
+    let stem = file_name.strip_suffix(".zip")?;
+    let parse = |stem: &str| -> Option<(String, NaiveDateTime)> {
+        let split = stem.len().checked_sub(20)?;
+        if !stem.is_char_boundary(split) || stem.as_bytes()[split] != b'_' { return None; }
+        let created = NaiveDateTime::parse_from_str(&stem[split + 1..], TIMESTAMP_FORMAT).ok()?;
+        Some((stem[..split].to_string(), created))
+    };
+    if let Some((world, created)) = parse(stem) { return Some((world, created, 1)); }
+    let (rest, seq) = stem.rsplit_once('-')?;
+    let (world, created) = parse(rest)?;
+    Some((world, created, seq.parse().ok().filter(|n| *n > 1)?))
+}
+
+fn validate_backup_name(file_name: &str) -> Result<(), String> {
+    if parse_backup_name(file_name).is_none() || file_name.contains(['/', '\\']) {
+        return Err(format!("'{}' is not a backup archive", file_name));
+    }
+    Ok(())
+}
+
+fn scan_backups(server_path: &str) -> Result<Vec<BackupFile>, String> {
+    let dir = backup_dir(server_path);
+    if !dir.exists() { return Ok(Vec::new()); }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+        if !metadata.is_file() { continue; }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if let Some((world, created, seq)) = parse_backup_name(&file_name) {
+            backups.push(BackupFile { file_name, world, created, seq, size: metadata.len() });
+        }
+    }
+    backups.sort_by_key(|b| std::cmp::Reverse((b.created, b.seq)));
+    Ok(backups)
+}
+
+fn load_catalog(server_path: &str) -> HashMap<String, CatalogEntry> {
+    fs::read_to_string(backup_dir(server_path).join(CATALOG_FILE)).ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_catalog(server_path: &str, catalog: &HashMap<String, CatalogEntry>) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(catalog).map_err(|e| e.to_string())?;
+    fs::write(backup_dir(server_path).join(CATALOG_FILE), content).map_err(|e| e.to_string())
+}
+
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 { break; }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Decides which of one world's backups (sorted newest first) survive the policy.
+fn retained_backups(backups: &[&BackupFile], policy: &RetentionPolicy) -> HashSet<String> {
+    let has_rules = policy.keep_last.is_some() || policy.keep_daily_days.is_some() || policy.keep_weekly_weeks.is_some();
+    let mut keep: HashSet<String> = if has_rules { HashSet::new() } else { backups.iter().map(|b| b.file_name.clone()).collect() };
+    let now = chrono::Local::now().naive_local();
+    if let Some(n) = policy.keep_last {
+        keep.extend(backups.iter().take(n).map(|b| b.file_name.clone()));
+    }
+    if let Some(days) = policy.keep_daily_days {
+        let mut seen_days = HashSet::new();
+        for b in backups.iter().filter(|b| now - b.created <= chrono::Duration::days(days as i64)) {
+            if seen_days.insert(b.created.date()) { keep.insert(b.file_name.clone()); }
+        }
+    }
+    if let Some(weeks) = policy.keep_weekly_weeks {
+        let mut seen_weeks = HashSet::new();
+        for b in backups.iter().filter(|b| now - b.created <= chrono::Duration::weeks(weeks as i64)) {
+            let week = b.created.iso_week();
+            if seen_weeks.insert((week.year(), week.week())) { keep.insert(b.file_name.clone()); }
+        }
+    }
+    if let Some(max_mb) = policy.max_total_mb {
+        let limit = max_mb * 1024 * 1024;
+        let mut total: u64 = backups.iter().filter(|b| keep.contains(&b.file_name)).map(|b| b.size).sum();
+        // Drop the oldest survivors first, but never the newest backup
+        for b in backups.iter().skip(1).rev() {
+            if total <= limit { break; }
+            if keep.remove(&b.file_name) { total -= b.size; }
+        }
+    }
+    keep
+}
+
+/// Deletes the world's backups not retained by `policy`, returning the removed file names.
+pub(crate) fn enforce_retention(server_path: &str, world_name: &str, policy: &RetentionPolicy) -> Result<Vec<String>, String> {
+    let backups = scan_backups(server_path)?;
+    let world_backups: Vec<&BackupFile> = backups.iter().filter(|b| b.world == world_name).collect();
+    let keep = retained_backups(&world_backups, policy);
+    let mut catalog = load_catalog(server_path);
+    let mut removed = Vec::new();
+    for b in world_backups.iter().filter(|b| !keep.contains(&b.file_name)) {
+        fs::remove_file(backup_dir(server_path).join(&b.file_name)).map_err(|e| e.to_string())?;
+        catalog.remove(&b.file_name);
+        removed.push(b.file_name.clone());
+    }
+    if !removed.is_empty() { save_catalog(server_path, &catalog)?; }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn backup_world(app: tauri::AppHandle, server_path: String, world_name: String) -> Result<String, String> {
+    // Waiting for the save and zipping block, so keep them off the async runtime
+    tauri::async_runtime::spawn_blocking(move || run_backup(&app, &app.state::<AppState>(), &server_path, &world_name))
+        .await.map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn list_backups(server_path: String) -> Result<Vec<BackupInfo>, String> {
+    // Hashing new multi-GB archives takes a while, so keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || backup_infos(&server_path)).await.map_err(|e| e.to_string())?
+}
+
+fn backup_infos(server_path: &str) -> Result<Vec<BackupInfo>, String> {
+    let backups = scan_backups(server_path)?;
+    let mut catalog = load_catalog(server_path);
+    let mut dirty = false;
+    let mut result = Vec::new();
+    for b in backups {
+        let cached = catalog.get(&b.file_name).filter(|c| c.size == b.size).map(|c| c.sha256.clone());
+        let checksum = match cached {
+            Some(sum) => sum,
+            None => {
+                let sum = sha256_file(&backup_dir(server_path).join(&b.file_name))?;
+                catalog.insert(b.file_name.clone(), CatalogEntry { size: b.size, sha256: sum.clone() });
+                dirty = true;
+                sum
+            }
+        };
+        result.push(BackupInfo {
+            file_name: b.file_name,
+            world: b.world,
+            timestamp: b.created.format("%Y-%m-%d %H:%M:%S").to_string(),
+            size_mb: (b.size as f64) / 1024.0 / 1024.0,
+            checksum,
+        });
+    }
+    if dirty { save_catalog(server_path, &catalog)?; }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_backup(server_path: String, file_name: String) -> Result<(), String> {
+    validate_backup_name(&file_name)?;
+    fs::remove_file(backup_dir(&server_path).join(&file_name)).map_err(|e| e.to_string())?;
+    let mut catalog = load_catalog(&server_path);
+    if catalog.remove(&file_name).is_some() { save_catalog(&server_path, &catalog)?; }
+    Ok(())
+}
+
+/// A scratch folder next to the worlds, so the final swap is a same-filesystem rename.
+pub(crate) fn staging_dir(server_path: &str) -> PathBuf {
+    Path::new(server_path).join(format!(".roam_restore_{}", chrono::Local::now().format(TIMESTAMP_FORMAT)))
+}
+
+/// Moves the current world (if any) into `roam_backups/pre_restore/` and renames `staging` into its place.
+/// `staging` is removed on failure. Returns the pre-restore snapshot path.
+pub(crate) fn swap_in_world(server_path: &str, staging: &Path, world_name: &str) -> Result<Option<PathBuf>, String> {
+    let world_dir = Path::new(server_path).join(world_name);
+    let mut snapshot = None;
+    if world_dir.exists() {
+        let snapshot_dir = backup_dir(server_path).join("pre_restore");
+        let timestamp = chrono::Local::now().format(TIMESTAMP_FORMAT).to_string();
+        let snapshot_path = snapshot_dir.join(format!("{}_{}", world_name, timestamp));
+        if let Err(e) = fs::create_dir_all(&snapshot_dir).and_then(|_| fs::rename(&world_dir, &snapshot_path)) {
+            let _ = fs::remove_dir_all(staging);
+            return Err(format!("Could not move the current world aside: {}", e));
+        }
+        snapshot = Some(snapshot_path);
+    }
+    if let Err(e) = fs::rename(staging, &world_dir) {
+        if let Some(snapshot_path) = &snapshot { let _ = fs::rename(snapshot_path, &world_dir); }
+        let _ = fs::remove_dir_all(staging);
+        return Err(format!("Could not swap in the restored world: {}", e));
+    }
+    Ok(snapshot)
+}
+
+/// Extracts a backup into `dest`, rejecting archives without a root `level.dat` or with entries escaping it.
+fn extract_world_archive(archive_path: &Path, dest: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        if entry.enclosed_name().is_none() { return Err(format!("Archive entry '{}' escapes the world folder", entry.name())); }
+    }
+    if archive.index_for_name("level.dat").is_none() { return Err("Archive does not contain a level.dat".into()); }
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let out_path = dest.join(entry.enclosed_name().unwrap());
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() { fs::create_dir_all(parent).map_err(|e| e.to_string())?; }
+        let mut out = fs::File::create(&out_path).map_err(|e| e.to_string())?;
+        io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn restore_backup(server_path: String, backup_file: String, target_world_name: String, state: State<'_, AppState>) -> Result<String, String> {
+    crate::ensure_server_stopped(&state, &server_path)?;
+    validate_backup_name(&backup_file)?;
+    crate::validate_folder_name(&target_world_name)?;
+    let archive_path = backup_dir(&server_path).join(&backup_file);
+    if !archive_path.exists() { return Err(format!("Backup '{}' not found", backup_file)); }
+
+    // Extracting and swapping a large world blocks, so keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        let staging = staging_dir(&server_path);
+        if let Err(e) = extract_world_archive(&archive_path, &staging) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+        let snapshot = swap_in_world(&server_path, &staging, &target_world_name)?;
+        Ok(snapshot.map(|p| p.to_string_lossy().to_string()).unwrap_or_default())
+    }).await.map_err(|e| e.to_string())?
+}
@@ -0,0 +1,196 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::nbt::{self, Compound, Tag};
+use crate::{worlds, AppState};
+
+#[derive(Serialize, Debug)]
+pub struct DatapackInfo {
+    /// The id the game uses, e.g. `file/terralith.zip`.
+    pub id: String,
+    pub file_name: String,
+    pub is_zip: bool,
+    pub description: Option<String>,
+    pub pack_format: Option<i64>,
+    pub enabled: bool,
+    /// Whether `pack.mcmeta` could be read; broken packs are ignored by the game.
+    pub valid: bool,
+}
+
+fn datapacks_dir(server_path: &str, world_name: &str) -> PathBuf {
+    Path::new(server_path).join(world_name).join("datapacks")
+}
+
+/// Flattens a JSON text component (string, object with `text`/`extra`, or array) to plain text.
+pub(crate) fn component_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(component_text).collect(),
+        Value::Object(map) => {
+            let mut text = map.get("text").or_else(|| map.get("translate")).map(component_text).unwrap_or_default();
+            if let Some(extra) = map.get("extra") { text.push_str(&component_text(extra)); }
+            text
+        }
+        other => other.to_string(),
+    }
+}
+
+pub(crate) fn read_mcmeta(path: &Path) -> Result<Value, String> {
+    let raw = if path.is_dir() {
+        fs::read_to_string(path.join("pack.mcmeta")).map_err(|e| e.to_string())?
+    } else {
+        let file = fs::File::open(path).map_err(|e| e.to_string())?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+        let mut entry = archive.by_name("pack.mcmeta").map_err(|_| "pack.mcmeta is missing from the archive root".to_string())?;
+        let mut raw = String::new();
+        entry.read_to_string(&mut raw).map_err(|e| e.to_string())?;
+        raw
+    };
+    let json: Value = serde_json::from_str(raw.trim_start_matches('\u{feff}')).map_err(|e| format!("Invalid pack.mcmeta: {}", e))?;
+    if !json.get("pack").is_some_and(Value::is_object) { return Err("pack.mcmeta has no \"pack\" section".into()); }
+    Ok(json)
+}
+
+/// `pack_format`, or the major version of `min_format` used by packs made for 1.21.9 and later.
+fn pack_format(pack: &Value) -> Option<i64> {
+    pack.get("pack_format").and_then(Value::as_i64).or_else(|| match pack.get("min_format")? {
+        Value::Array(parts) => parts.first().and_then(Value::as_i64),
+        other => other.as_i64(),
+    })
+}
+
+fn pack_id(file_name: &str) -> String {
+    format!("file/{}", file_name)
+}
+
+/// Quotes a pack id for a `datapack` command argument.
+fn quoted(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Checks that `file_name` is an existing entry of the world's datapacks folder.
+fn pack_path(server_path: &str, world_name: &str, file_name: &str) -> Result<PathBuf, String> {
+    crate::validate_folder_name(world_name)?;
+    crate::validate_folder_name(file_name)?;
+    let path = datapacks_dir(server_path, world_name).join(file_name);
+    if !path.exists() { return Err(format!("Datapack '{}' not found", file_name)); }
+    Ok(path)
+}
+
+fn world_is_live(state: &AppState, server_path: &str, world_name: &str) -> bool {
+    world_name == worlds::active_world_name(server_path) && crate::is_server_running(state, server_path)
+}
+
+/// Moves `id` between `DataPacks.Enabled` and `DataPacks.Disabled`, creating the lists if needed.
+fn set_pack_state(data: &mut Compound, id: &str, enabled: bool) {
+    if data.compound("DataPacks").is_none() { data.insert("DataPacks", Tag::Compound(Compound::default())); }
+    let packs = data.compound_mut("DataPacks").unwrap();
+    for (key, keep) in [("Enabled", enabled), ("Disabled", !enabled)] {
+        if packs.get(key).is_none() { packs.insert(key, Tag::List(8, Vec::new())); }
+        if let Some(Tag::List(item_id, items)) = packs.get_mut(key) {
+            *item_id = 8;
+            items.retain(|t| t.as_str() != Some(id));
+            if keep { items.push(Tag::String(id.to_string())); }
+        }
+    }
+}
+
+/// Lists the zip and folder datapacks installed in a world with their enabled state from `level.dat`.
+#[tauri::command]
+pub async fn list_datapacks(server_path: String, world_name: String) -> Result<Vec<DatapackInfo>, String> {
+    crate::validate_folder_name(&world_name)?;
+    let data = worlds::read_level_data(&Path::new(&server_path).join(&world_name))?;
+    let info = worlds::level_info(&data);
+    let dir = datapacks_dir(&server_path, &world_name);
+    let Ok(entries) = fs::read_dir(&dir) else { return Ok(Vec::new()); };
+
+    let mut packs = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let is_zip = path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+        if !is_zip && !path.is_dir() { continue; }
+        let meta = read_mcmeta(&path).ok();
+        let pack = meta.as_ref().and_then(|m| m.get("pack"));
+        let id = pack_id(&file_name);
+        packs.push(DatapackInfo {
+            enabled: info.enabled_datapacks.contains(&id),
+            id,
+            file_name,
+            is_zip,
+            description: pack.and_then(|p| p.get("description")).map(component_text),
+            pack_format: pack.and_then(pack_format),
+            valid: meta.is_some(),
+        });
+    }
+    packs.sort_by_key(|p| p.file_name.to_lowercase());
+    Ok(packs)
+}
+
+/// Copies a datapack zip into the world. A running server picks it up through `reload`.
+#[tauri::command]
+pub async fn install_datapack(server_path: String, world_name: String, source: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    crate::validate_folder_name(&world_name)?;
+    if !Path::new(&server_path).join(&world_name).join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+    let source_path = Path::new(&source);
+    if !source_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) { return Err("Select a datapack .zip file".into()); }
+    read_mcmeta(source_path)?;
+    let file_name = source_path.file_name().ok_or("Invalid file name")?.to_string_lossy().to_string();
+    let dir = datapacks_dir(&server_path, &world_name);
+    let target = dir.join(&file_name);
+    if target.exists() { return Err(format!("A datapack named '{}' is already installed", file_name)); }
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::copy(source_path, &target).map_err(|e| e.to_string())?;
+
+    let mut done = vec![format!("Installed {} into {}/datapacks", file_name, world_name)];
+    if world_is_live(&state, &server_path, &world_name) {
+        match crate::run_live(&state, &server_path, vec!["reload".into()])? {
+            Some(sent) => done.extend(sent),
+            None => done.push("Run 'reload' in the server console to load it".into()),
+        }
+    }
+    Ok(done)
+}
+
+/// Deletes a datapack, disabling it first when the world is loaded.
+#[tauri::command]
+pub async fn remove_datapack(server_path: String, world_name: String, file_name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let path = pack_path(&server_path, &world_name, &file_name)?;
+    let id = pack_id(&file_name);
+    let mut done = Vec::new();
+    if world_is_live(&state, &server_path, &world_name) {
+        let command = format!("datapack disable {}", quoted(&id));
+        match crate::run_live(&state, &server_path, vec![command.clone()])? {
+            Some(sent) => done.extend(sent),
+            None => done.push(format!("Run '{}' in the server console before it next saves", command)),
+        }
+    }
+    if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) }.map_err(|e| e.to_string())?;
+    done.push(format!("Removed {} from {}/datapacks", file_name, world_name));
+    Ok(done)
+}
+
+/// Enables or disables a datapack with `datapack enable/disable` while the world is loaded,
+/// otherwise by editing the `DataPacks` lists in `level.dat`.
+#[tauri::command]
+pub async fn set_datapack_enabled(server_path: String, world_name: String, file_name: String, enabled: bool, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let path = pack_path(&server_path, &world_name, &file_name)?;
+    read_mcmeta(&path)?;
+    let id = pack_id(&file_name);
+    if world_is_live(&state, &server_path, &world_name) {
+        let command = format!("datapack {} {}", if enabled { "enable" } else { "disable" }, quoted(&id));
+        return crate::run_live(&state, &server_path, vec![command])?
+            .ok_or_else(|| "The server is running outside the manager and RCON is not enabled. Stop it or enable RCON to change datapacks.".to_string());
+    }
+
+    let level_path = Path::new(&server_path).join(&world_name).join("level.dat");
+    let (root_name, mut root) = nbt::read_file(&level_path)?;
+    let data = root.compound_mut("Data").ok_or("level.dat has no Data compound")?;
+    set_pack_state(data, &id, enabled);
+    nbt::write_file(&level_path, &root_name, &root)?;
+    Ok(vec![format!("Updated {}/level.dat (previous copy kept as level.dat_old)", world_name)])
+}
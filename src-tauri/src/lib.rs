@@ -1,14 +1,34 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::io::{BufRead, BufReader, Write, Read};
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use sysinfo::{Pid, System};
 use tauri::{Emitter, Manager, State, Window, WindowEvent};
-use walkdir::WalkDir;
-use zip::write::SimpleFileOptions;
+
+mod access_lists;
+mod advancements;
+mod anvil;
+mod backup_scheduler;
+mod backup_store;
+mod backups;
+mod datapacks;
+mod nbt;
+mod player_data;
+mod players;
+mod profiles;
+mod properties;
+mod property_schema;
+mod rcon;
+mod resource_pack;
+mod sessions;
+mod stats;
+mod world_transfer;
+mod worlds;
+mod yaml_config;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TunnelConfig {
@@ -25,6 +45,21 @@ pub struct ServerConfig {
     pub min_ram: String,
     pub max_ram: String,
     pub tunnel: Option<TunnelConfig>,
+    /// Persisted with the rest of the config by the frontend (`serverStore.updateRetention`) and
+    /// pushed here through `set_server_config`.
+    #[serde(default)]
+    pub retention: Option<backups::RetentionPolicy>,
+    #[serde(default)]
+    pub backup_schedule: Option<backup_scheduler::BackupSchedule>,
+    /// Port of the built-in resource pack server, restarted with the server.
+    #[serde(default)]
+    pub resource_pack_port: Option<u16>,
+    /// Base URL for name/UUID lookups on online-mode servers; Mojang's API when unset.
+    #[serde(default)]
+    pub profile_api_url: Option<String>,
+    /// Lets player lists ask the profile API for names missing from the caches. Off by default.
+    #[serde(default)]
+    pub profile_lookups: bool,
 }
 
 #[derive(Serialize, Clone, Debug, PartialEq)]
@@ -35,6 +70,14 @@ pub enum ServerStatus {
     Stopping,
 }
 
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum ServerFlavor {
+    Vanilla,
+    Bukkit,
+    Spigot,
+    Paper,
+}
+
 #[derive(Serialize, Clone, Debug, PartialEq)]
 pub enum TunnelStatus {
     Offline,
@@ -50,6 +93,7 @@ pub struct ServerStats {
     pub memory: u64,
     pub status: ServerStatus,
     pub player_count: i32,
+    pub players: Vec<String>,
     pub tunnel_status: TunnelStatus,
 }
 
@@ -61,21 +105,20 @@ pub struct PlayerInfo {
     pub steps: u64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct WorldInfo {
-    pub name: String,
-    pub size_mb: f64,
-    pub last_modified: String,
-}
-
 pub struct AppState {
     pub config: Mutex<Option<ServerConfig>>,
     pub child_process: Mutex<Option<Child>>,
     pub tunnel_process: Mutex<Option<Child>>,
-    pub player_count: Arc<Mutex<i32>>,
+    /// Names of online players, from join/leave lines and periodic reconciliation.
+    pub online_players: Arc<Mutex<BTreeSet<String>>>,
     pub status: Arc<Mutex<ServerStatus>>,
     pub tunnel_status: Arc<Mutex<TunnelStatus>>,
     pub sys: Mutex<System>,
+    /// Notified by the log reader when the server prints "Saved the game".
+    pub save_waiter: Arc<Mutex<Option<Sender<()>>>>,
+    /// Server paths where a player joined since the last successful scheduled backup.
+    pub players_seen: Arc<Mutex<HashSet<String>>>,
+    pub pack_server: Mutex<Option<resource_pack::PackServer>>,
 }
 
 fn find_orphaned_java_process(server_path: &str) -> Option<Pid> {
@@ -95,6 +138,72 @@ fn find_orphaned_java_process(server_path: &str) -> Option<Pid> {
     None
 }
 
+/// True when the configured server at `server_path` was launched by this manager and is still attached.
+fn is_managed_server(state: &AppState, server_path: &str) -> bool {
+    let same_server = state.config.lock().unwrap().as_ref().is_some_and(|c| c.path == server_path);
+    same_server && state.child_process.lock().unwrap().as_mut().is_some_and(|c| matches!(c.try_wait(), Ok(None)))
+}
+
+fn is_server_running(state: &AppState, server_path: &str) -> bool {
+    is_managed_server(state, server_path) || find_orphaned_java_process(server_path).is_some()
+}
+
+/// Refuses file-level world operations while any Java process is using the server folder.
+fn ensure_server_stopped(state: &AppState, server_path: &str) -> Result<(), String> {
+    if is_managed_server(state, server_path) { return Err("Stop the server before doing this.".into()); }
+    if let Some(pid) = find_orphaned_java_process(server_path) {
+        return Err(format!("Server is still running (PID {}). Stop it before doing this.", pid));
+    }
+    Ok(())
+}
+
+/// Rejects names that would escape the server folder when joined onto it.
+fn validate_folder_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(format!("'{}' is not a valid folder name", name));
+    }
+    Ok(())
+}
+
+fn write_to_stdin(state: &AppState, command: &str) -> Result<(), String> {
+    let mut child_process = state.child_process.lock().unwrap();
+    if let Some(child) = child_process.as_mut() {
+        let stdin = child.stdin.as_mut().ok_or("Failed to open stdin")?;
+        let cmd_with_newline = format!("{}\n", command.trim());
+        stdin.write_all(cmd_with_newline.as_bytes()).map_err(|e: std::io::Error| e.to_string())?;
+        stdin.flush().map_err(|e: std::io::Error| e.to_string())?;
+        Ok(())
+    } else {
+        Err("Cannot send commands to an orphaned process. Only servers started via this manager support direct commands.".into())
+    }
+}
+
+/// Sends the commands when the server is up, through stdin if it was started here and RCON
+/// otherwise. Returns `None` when it is stopped, or runs outside the manager without RCON, so the
+/// caller can fall back to editing files.
+fn run_live(state: &AppState, server_path: &str, commands: Vec<String>) -> Result<Option<Vec<String>>, String> {
+    if is_managed_server(state, server_path) {
+        for command in &commands { write_to_stdin(state, command)?; }
+        return Ok(Some(commands));
+    }
+    if find_orphaned_java_process(server_path).is_none() { return Ok(None); }
+    let Some(client) = rcon::RconClient::from_properties(server_path) else { return Ok(None); };
+    let mut client = client?;
+    for command in &commands { client.command(command)?; }
+    Ok(Some(commands))
+}
+
+fn load_properties(path: &str) -> HashMap<String, String> {
+    properties::Properties::load(path).to_map()
+}
+
+/// Replaces (or appends) one key in `server.properties`, leaving every other line untouched.
+fn set_property(path: &str, key: &str, value: &str) -> Result<(), String> {
+    let mut props = properties::Properties::load(path);
+    props.set(key, value);
+    props.save(path)
+}
+
 #[tauri::command]
 async fn start_tunnel(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     let config = {
@@ -154,7 +263,8 @@ async fn start_tunnel(app: tauri::AppHandle, state: State<'_, AppState>) -> Resu
 
 #[tauri::command]
 async fn get_server_stats(state: State<'_, AppState>) -> Result<ServerStats, String> {
-    let pc = *state.player_count.lock().unwrap();
+    let players: Vec<String> = state.online_players.lock().unwrap().iter().cloned().collect();
+    let pc = players.len() as i32;
     let mut status = state.status.lock().unwrap().clone();
     let tunnel_status = state.tunnel_status.lock().unwrap().clone();
     let mut sys = state.sys.lock().unwrap();
@@ -177,6 +287,7 @@ async fn get_server_stats(state: State<'_, AppState>) -> Result<ServerStats, Str
                         memory: process.memory(), 
                         status, 
                         player_count: pc,
+                        players,
                         tunnel_status
                     });
                 }
@@ -196,64 +307,14 @@ async fn get_server_stats(state: State<'_, AppState>) -> Result<ServerStats, Str
                     memory: process.memory(), 
                     status, 
                     player_count: pc,
+                    players,
                     tunnel_status
                 });
             }
         }
     }
 
-    Ok(ServerStats { cpu: 0.0, core_count, memory: 0, status: ServerStatus::Offline, player_count: pc, tunnel_status })
-}
-
-#[tauri::command]
-async fn get_worlds(path: String) -> Result<Vec<WorldInfo>, String> {
-    let mut worlds = Vec::new();
-    let entries = fs::read_dir(&path).map_err(|e| e.to_string())?;
-    for entry in entries {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        if path.is_dir() && path.join("level.dat").exists() {
-            let name = path.file_name().unwrap().to_string_lossy().to_string();
-            let metadata = fs::metadata(&path).map_err(|e| e.to_string())?;
-            let last_modified = if let Ok(time) = metadata.modified() {
-                let dt: chrono::DateTime<chrono::Local> = time.into();
-                dt.format("%d %b %Y, %H:%M").to_string()
-            } else { "Unknown".to_string() };
-            let total_size: u64 = WalkDir::new(&path).into_iter().filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).filter(|m| m.is_file()).map(|m| m.len()).sum();
-            worlds.push(WorldInfo { name, size_mb: (total_size as f64) / 1024.0 / 1024.0, last_modified });
-        }
-    }
-    Ok(worlds)
-}
-
-#[tauri::command]
-async fn backup_world(server_path: String, world_name: String) -> Result<String, String> {
-    let world_dir = Path::new(&server_path).join(&world_name);
-    let backup_dir = Path::new(&server_path).join("roam_backups");
-    if !backup_dir.exists() { fs::create_dir_all(&backup_dir).map_err(|e| e.to_string())?; }
-    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
-    let backup_filename = format!("{}_{}.zip", world_name, timestamp);
-    let backup_path = backup_dir.join(&backup_filename);
-    let file = fs::File::create(&backup_path).map_err(|e| e.to_string())?;
-    let mut zip = zip::ZipWriter::new(file);
-    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated).unix_permissions(0o755);
-    let mut buffer = Vec::new();
-    for entry in WalkDir::new(&world_dir) {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        let name = path.strip_prefix(&world_dir).map_err(|e| e.to_string())?;
-        if path.is_file() {
-            zip.start_file(name.to_string_lossy().to_string(), options).map_err(|e| e.to_string())?;
-            let mut f = fs::File::open(path).map_err(|e| e.to_string())?;
-            f.read_to_end(&mut buffer).map_err(|e| e.to_string())?;
-            zip.write_all(&buffer).map_err(|e| e.to_string())?;
-            buffer.clear();
-        } else if !name.as_os_str().is_empty() {
-            zip.add_directory(name.to_string_lossy().to_string(), options).map_err(|e| e.to_string())?;
-        }
-    }
-    zip.finish().map_err(|e| e.to_string())?;
-    Ok(backup_filename)
+    Ok(ServerStats { cpu: 0.0, core_count, memory: 0, status: ServerStatus::Offline, player_count: pc, players, tunnel_status })
 }
 
 #[tauri::command]
@@ -267,7 +328,7 @@ async fn select_jar_file(app: tauri::AppHandle) -> Result<Option<ServerConfig>,
         let path = Path::new(&path_str);
         let parent = path.parent().ok_or("Invalid path")?.to_string_lossy().to_string();
         let file_name = path.file_name().ok_or("Invalid filename")?.to_string_lossy().to_string();
-        Ok(Some(ServerConfig { name: None, path: parent, jar_name: file_name, min_ram: "1G".into(), max_ram: "2G".into(), tunnel: None }))
+        Ok(Some(ServerConfig { name: None, path: parent, jar_name: file_name, min_ram: "1G".into(), max_ram: "2G".into(), tunnel: None, retention: None, backup_schedule: None, resource_pack_port: None, profile_api_url: None, profile_lookups: false }))
     } else { Ok(None) }
 }
 
@@ -289,6 +350,12 @@ async fn start_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Resu
     }
     let mut child_process = state.child_process.lock().unwrap();
     if child_process.is_some() { return Err("Server already running".into()); }
+
+    if let Some(port) = config.resource_pack_port {
+        if let Err(e) = resource_pack::ensure_serving(&state, &config.path, port) {
+            app.emit("server-log", format!("[System] Resource pack server not started: {}", e)).unwrap();
+        }
+    }
     
     // Start Tunnel if configured
     if let Some(tunnel) = config.tunnel.as_ref() {
@@ -321,29 +388,45 @@ async fn start_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Resu
             format!("Failed to start: {}", e)
         })?;
     let stdout = child.stdout.take().unwrap();
-    let player_count = Arc::clone(&state.player_count);
+    let online_players = Arc::clone(&state.online_players);
+    players::update_online(&app, &online_players, BTreeSet::clear);
     let status_clone = Arc::clone(&state.status);
+    let save_waiter = Arc::clone(&state.save_waiter);
+    let players_seen = Arc::clone(&state.players_seen);
+    let server_path = config.path.clone();
     let app_clone = app.clone();
     std::thread::spawn(move || {
+        sessions::import_if_missing(&server_path);
         let reader = BufReader::new(stdout);
         let mut started = false;
-        for line in reader.lines() {
-            if let Ok(l) = line {
-                app_clone.emit("server-log", &l).unwrap();
-                if !started && (l.contains("Done") || l.contains("For help, type \"help\"")) {
-                    started = true;
-                    let mut status = status_clone.lock().unwrap();
-                    *status = ServerStatus::Running;
-                    app_clone.emit("status-update", ServerStatus::Running).unwrap();
-                }
-                let mut pc = player_count.lock().unwrap();
-                if l.contains("joined the game") {
-                    *pc += 1; app_clone.emit("player-update", *pc).unwrap();
-                } else if l.contains("left the game") {
-                    if *pc > 0 { *pc -= 1; } app_clone.emit("player-update", *pc).unwrap();
+        let mut list_parser = players::ListParser::default();
+        for l in reader.lines().map_while(Result::ok) {
+            app_clone.emit("server-log", &l).unwrap();
+            if !started && (l.contains("Done") || l.contains("For help, type \"help\"")) {
+                started = true;
+                let mut status = status_clone.lock().unwrap();
+                *status = ServerStatus::Running;
+                app_clone.emit("status-update", ServerStatus::Running).unwrap();
+            }
+            // Exact match so a chat message quoting it cannot end the wait early
+            if sessions::console_message(&l) == Some("Saved the game") {
+                if let Some(tx) = save_waiter.lock().unwrap().take() { let _ = tx.send(()); }
+            }
+            if let Some(names) = list_parser.feed(&l) {
+                players::update_online(&app_clone, &online_players, |set| *set = names);
+            } else if let Some(event) = sessions::parse_event(&l) {
+                match &event {
+                    sessions::Event::Join(name) => {
+                        players_seen.lock().unwrap().insert(server_path.clone());
+                        players::update_online(&app_clone, &online_players, |set| { set.insert(name.clone()); });
+                    }
+                    sessions::Event::Leave(name) => players::update_online(&app_clone, &online_players, |set| { set.remove(name); }),
                 }
+                sessions::record(&server_path, &event);
             }
         }
+        sessions::close_open(&server_path);
+        players::update_online(&app_clone, &online_players, BTreeSet::clear);
         let mut status = status_clone.lock().unwrap();
         *status = ServerStatus::Offline;
         app_clone.emit("status-update", ServerStatus::Offline).unwrap();
@@ -352,6 +435,20 @@ async fn start_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Resu
     Ok(())
 }
 
+/// How long a server gets to save and exit after `stop` before it is killed.
+const STOP_TIMEOUT_SECS: u64 = 60;
+
+/// Sends `stop` and waits for the server to save and exit. Returns `false` if it is still running.
+fn stop_gracefully(child: &mut Child) -> bool {
+    let Some(stdin) = child.stdin.as_mut() else { return false; };
+    if stdin.write_all(b"stop\n").and_then(|_| stdin.flush()).is_err() { return false; }
+    (0..STOP_TIMEOUT_SECS * 2).any(|_| {
+        if matches!(child.try_wait(), Ok(Some(_))) { return true; }
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        false
+    })
+}
+
 #[tauri::command]
 async fn stop_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<bool, String> {
     {
@@ -371,10 +468,13 @@ async fn stop_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Resul
         app.emit("tunnel-status-update", TunnelStatus::Offline).unwrap();
     }
 
+    // A stop backup needs the world on disk, so let the server save and exit before killing it
+    let stop_backup = state.config.lock().unwrap().as_ref().and_then(|c| c.backup_schedule.clone()).filter(|s| s.on_stop && !s.worlds.is_empty());
     let mut child_process = state.child_process.lock().unwrap();
     let mut stopped = false;
     if let Some(mut child) = child_process.take() {
-        child.kill().map_err(|e| e.to_string())?;
+        let exited = stop_backup.is_some() && stop_gracefully(&mut child);
+        if !exited { child.kill().map_err(|e| e.to_string())?; }
         let _ = child.wait();
         stopped = true;
     } else {
@@ -385,8 +485,15 @@ async fn stop_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Resul
         if let Some(pid) = find_orphaned_java_process(&config.path) {
             let mut sys = System::new_all();
             sys.refresh_all();
-            if let Some(process) = sys.process(pid) {
-                process.kill();
+            if sys.process(pid).is_some() {
+                let rcon = stop_backup.as_ref().and_then(|_| rcon::RconClient::from_properties(&config.path));
+                let asked = rcon.is_some_and(|client| client.and_then(|mut c| c.command("stop")).is_ok());
+                let exited = asked && (0..STOP_TIMEOUT_SECS * 2).any(|_| {
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                    sys.refresh_all();
+                    sys.process(pid).is_none()
+                });
+                if !exited { if let Some(process) = sys.process(pid) { process.kill(); } }
                 let mut attempts = 0;
                 while attempts < 10 {
                     std::thread::sleep(std::time::Duration::from_millis(500));
@@ -403,31 +510,28 @@ async fn stop_server(app: tauri::AppHandle, state: State<'_, AppState>) -> Resul
         *status = ServerStatus::Offline;
         app.emit("status-update", ServerStatus::Offline).unwrap();
     }
+
+    // Backup on stop runs in the background so the UI is not held up by large worlds
+    let config = state.config.lock().unwrap().clone();
+    if let Some(config) = config.filter(|_| stopped) {
+        if let Some(schedule) = config.backup_schedule.filter(|s| s.on_stop && !s.worlds.is_empty()) {
+            let app_clone = app.clone();
+            std::thread::spawn(move || backup_scheduler::run_scheduled(&app_clone, &config.path, &schedule, "Stop"));
+        }
+    }
     Ok(stopped)
 }
 
 #[tauri::command]
 async fn read_properties(path: String) -> Result<HashMap<String, String>, String> {
-    let prop_path = Path::new(&path).join("server.properties");
-    if !prop_path.exists() { return Ok(HashMap::new()); }
-    let content = fs::read_to_string(prop_path).map_err(|e| e.to_string())?;
-    let mut props = HashMap::new();
-    for line in content.lines() {
-        let line = line.trim();
-        if line.starts_with('#') || !line.contains('=') { continue; }
-        if let Some((key, value)) = line.split_once('=') {
-            props.insert(key.trim().to_string(), value.trim().to_string());
-        }
-    }
-    Ok(props)
+    Ok(load_properties(&path))
 }
 
 #[tauri::command]
 async fn write_properties(path: String, props: HashMap<String, String>) -> Result<(), String> {
-    let prop_path = Path::new(&path).join("server.properties");
-    let mut content = String::from("# Updated by Roam MC Manager\n");
-    for (key, value) in props { content.push_str(&format!("{}={}\n", key, value)); }
-    fs::write(prop_path, content).map_err(|e| e.to_string())
+    // Merges into the existing file; keys missing from `props` are kept (use `patch_properties` to remove)
+    property_schema::validate(&props)?;
+    properties::patch(&path, &props, &[])
 }
 
 #[tauri::command]
@@ -441,7 +545,7 @@ fn maximize_window(window: Window) {
 }
 
 #[tauri::command]
-async fn get_players_data(path: String) -> Result<Vec<PlayerInfo>, String> {
+async fn get_players_data(path: String, state: State<'_, AppState>) -> Result<Vec<PlayerInfo>, String> {
     let prop_path = Path::new(&path).join("server.properties");
     let mut world_name = String::from("world");
     if prop_path.exists() {
@@ -455,40 +559,29 @@ async fn get_players_data(path: String) -> Result<Vec<PlayerInfo>, String> {
         }
     }
     let stats_path = Path::new(&path).join(&world_name).join("stats");
-    let cache_path = Path::new(&path).join("usercache.json");
     if !stats_path.exists() { return Ok(Vec::new()); }
-    let mut uuid_to_name = HashMap::new();
-    if cache_path.exists() {
-        if let Ok(cache_content) = fs::read_to_string(cache_path) {
-            if let Ok(cache) = serde_json::from_str::<serde_json::Value>(&cache_content) {
-                if let Some(arr) = cache.as_array() {
-                    for entry in arr {
-                        if let (Some(u), Some(n)) = (entry["uuid"].as_str(), entry["name"].as_str()) {
-                            uuid_to_name.insert(u.to_string(), n.to_string());
-                        }
-                    }
-                }
-            }
+    let mut stat_files = Vec::new();
+    for entry in fs::read_dir(stats_path).map_err(|e| e.to_string())? {
+        let file_path = entry.map_err(|e| e.to_string())?.path();
+        if file_path.extension().is_some_and(|ext| ext == "json") {
+            stat_files.push((file_path.file_stem().unwrap().to_string_lossy().to_string(), file_path));
         }
     }
+    let uuids: Vec<String> = stat_files.iter().map(|(uuid, _)| uuid.clone()).collect();
+    let uuid_to_name = profiles::names_for_uuids(&state, &path, &uuids).await;
     let mut players = Vec::new();
-    for entry in fs::read_dir(stats_path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let file_path = entry.path();
-        if file_path.extension().map_or(false, |ext| ext == "json") {
-            let uuid = file_path.file_stem().unwrap().to_string_lossy().to_string();
-            let name = uuid_to_name.get(&uuid).cloned().unwrap_or_else(|| uuid.clone());
-            if let Ok(content) = fs::read_to_string(&file_path) {
-                if let Ok(stats) = serde_json::from_str::<serde_json::Value>(&content) {
-                    let custom = &stats["stats"]["minecraft:custom"];
-                    let ticks = custom["minecraft:play_time"].as_u64()
-                        .or(custom["minecraft:play_one_minute"].as_u64())
-                        .unwrap_or(0);
-                    let hours = (ticks as f64) / 20.0 / 3600.0;
-                    let cm_walked = custom["minecraft:walk_one_cm"].as_u64().unwrap_or(0);
-                    let steps = cm_walked / 75;
-                    players.push(PlayerInfo { uuid, name, time_played: hours, steps });
-                }
+    for (uuid, file_path) in stat_files {
+        let name = uuid_to_name.get(&uuid).cloned().unwrap_or_else(|| uuid.clone());
+        if let Ok(content) = fs::read_to_string(&file_path) {
+            if let Ok(stats) = serde_json::from_str::<serde_json::Value>(&content) {
+                let custom = &stats["stats"]["minecraft:custom"];
+                let ticks = custom["minecraft:play_time"].as_u64()
+                    .or(custom["minecraft:play_one_minute"].as_u64())
+                    .unwrap_or(0);
+                let hours = (ticks as f64) / 20.0 / 3600.0;
+                let cm_walked = custom["minecraft:walk_one_cm"].as_u64().unwrap_or(0);
+                let steps = cm_walked / 75;
+                players.push(PlayerInfo { uuid, name, time_played: hours, steps });
             }
         }
     }
@@ -497,16 +590,7 @@ async fn get_players_data(path: String) -> Result<Vec<PlayerInfo>, String> {
 
 #[tauri::command]
 async fn send_server_command(command: String, state: State<'_, AppState>) -> Result<(), String> {
-    let mut child_process = state.child_process.lock().unwrap();
-    if let Some(child) = child_process.as_mut() {
-        let stdin = child.stdin.as_mut().ok_or("Failed to open stdin")?;
-        let cmd_with_newline = format!("{}\n", command.trim());
-        stdin.write_all(cmd_with_newline.as_bytes()).map_err(|e: std::io::Error| e.to_string())?;
-        stdin.flush().map_err(|e: std::io::Error| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Cannot send commands to an orphaned process. Only servers started via this manager support direct commands.".into())
-    }
+    write_to_stdin(&state, &command)
 }
 
 #[tauri::command]
@@ -534,6 +618,23 @@ fn delete_directory(path: String) -> Result<(), String> {
     fs::remove_dir_all(path).map_err(|e| e.to_string())
 }
 
+/// Guesses the server software from its generated config files, falling back to the jar name
+/// for servers that have not been started yet.
+fn detect_server_flavor(path: &str, jar_name: Option<&str>) -> ServerFlavor {
+    let root = Path::new(path);
+    let jar = jar_name.unwrap_or_default().to_lowercase();
+    if root.join("config").join("paper-global.yml").exists() || root.join("paper.yml").exists()
+        || ["paper", "purpur", "folia", "pufferfish"].iter().any(|n| jar.contains(n)) {
+        ServerFlavor::Paper
+    } else if root.join("spigot.yml").exists() || jar.contains("spigot") {
+        ServerFlavor::Spigot
+    } else if root.join("bukkit.yml").exists() || jar.contains("bukkit") {
+        ServerFlavor::Bukkit
+    } else {
+        ServerFlavor::Vanilla
+    }
+}
+
 #[tauri::command]
 fn is_server_initialized(path: String) -> bool {
     let prop_path = Path::new(&path).join("server.properties");
@@ -547,10 +648,18 @@ pub fn run() {
             config: Mutex::new(None),
             child_process: Mutex::new(None),
             tunnel_process: Mutex::new(None),
-            player_count: Arc::new(Mutex::new(0)),
+            online_players: Arc::new(Mutex::new(BTreeSet::new())),
             status: Arc::new(Mutex::new(ServerStatus::Offline)),
             tunnel_status: Arc::new(Mutex::new(TunnelStatus::Offline)),
             sys: Mutex::new(System::new_all()),
+            save_waiter: Arc::new(Mutex::new(None)),
+            players_seen: Arc::new(Mutex::new(HashSet::new())),
+            pack_server: Mutex::new(None),
+        })
+        .setup(|app| {
+            backup_scheduler::spawn(app.handle().clone());
+            players::spawn(app.handle().clone());
+            Ok(())
         })
         .on_window_event(|window, event| {
             if let WindowEvent::Destroyed = event {
@@ -574,10 +683,23 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
             set_server_config, start_server, stop_server, get_server_stats,
-            read_properties, write_properties, select_jar_file,
+            read_properties, write_properties, properties::patch_properties, property_schema::get_properties_schema,
+            yaml_config::list_config_files, yaml_config::read_config_file, yaml_config::write_config_values, select_jar_file,
             close_window, minimize_window, maximize_window,
             is_server_initialized, delete_directory, get_players_data,
-            send_server_command, open_folder, get_worlds, backup_world,
+            send_server_command, open_folder, worlds::get_worlds, worlds::update_world_settings, worlds::set_active_world, worlds::clone_world, worlds::rename_world, worlds::delete_world,
+            world_transfer::import_world, world_transfer::export_world, anvil::analyze_world, anvil::trim_world,
+            datapacks::list_datapacks, datapacks::install_datapack, datapacks::remove_datapack, datapacks::set_datapack_enabled,
+            resource_pack::set_resource_pack, resource_pack::stop_resource_pack_server,
+            access_lists::get_access_lists, access_lists::whitelist_add, access_lists::whitelist_remove, access_lists::op_add, access_lists::op_remove,
+            access_lists::ban_player, access_lists::pardon_player, access_lists::ban_ip, access_lists::pardon_ip, profiles::resolve_player_uuid,
+            stats::get_player_stats, stats::get_stat_leaderboard,
+            advancements::get_player_advancements, advancements::get_advancement_summary, advancements::list_vanilla_advancements, player_data::get_player_data,
+            sessions::get_player_sessions, sessions::get_peak_players, sessions::get_activity_by_hour, backups::backup_world,
+            backups::list_backups, backups::delete_backup, backups::restore_backup,
+            backup_store::snapshot_world, backup_store::list_snapshots, backup_store::delete_snapshot,
+            backup_store::restore_snapshot, backup_store::prune_backup_store, backup_store::verify_backup_store,
+            backup_scheduler::preview_backup_schedule,
             start_tunnel, find_binary
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,356 @@
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// A single NBT value. Lists keep their element type so empty lists round-trip unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(u8, Vec<Tag>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+/// Compound tag that keeps entries in file order, so rewriting `level.dat` does not shuffle it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Compound(pub Vec<(String, Tag)>);
+
+impl Compound {
+    pub fn get(&self, key: &str) -> Option<&Tag> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Tag> {
+        self.0.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn insert(&mut self, key: &str, value: Tag) {
+        match self.get_mut(key) {
+            Some(existing) => *existing = value,
+            None => self.0.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Tag> {
+        let index = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(index).1)
+    }
+
+    /// Follows a dotted path of nested compounds, e.g. `"Data.Version.Name"`.
+    pub fn path(&self, path: &str) -> Option<&Tag> {
+        let mut parts = path.split('.');
+        let mut current = self.get(parts.next()?)?;
+        for part in parts { current = current.as_compound()?.get(part)?; }
+        Some(current)
+    }
+
+    pub fn compound(&self, key: &str) -> Option<&Compound> {
+        self.get(key)?.as_compound()
+    }
+
+    pub fn compound_mut(&mut self, key: &str) -> Option<&mut Compound> {
+        match self.get_mut(key)? { Tag::Compound(c) => Some(c), _ => None }
+    }
+}
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(..) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+
+    /// Any integral tag widened to `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Tag::Byte(v) => Some(*v as i64),
+            Tag::Short(v) => Some(*v as i64),
+            Tag::Int(v) => Some(*v as i64),
+            Tag::Long(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Tag::Float(v) => Some(*v as f64),
+            Tag::Double(v) => Some(*v),
+            _ => self.as_i64().map(|v| v as f64),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self { Tag::String(s) => Some(s), _ => None }
+    }
+
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self { Tag::Compound(c) => Some(c), _ => None }
+    }
+
+    pub fn as_list(&self) -> Option<&[Tag]> {
+        match self { Tag::List(_, items) => Some(items), _ => None }
+    }
+
+    /// Integer arrays and lists of integers both hold coordinates depending on the game version.
+    pub fn as_int_vec(&self) -> Option<Vec<i64>> {
+        match self {
+            Tag::IntArray(v) => Some(v.iter().map(|i| *i as i64).collect()),
+            Tag::List(_, items) => items.iter().map(|t| t.as_i64()).collect(),
+            _ => None,
+        }
+    }
+
+    /// Renders scalar tags the way the game prints them in commands (`true`, `12`, `text`).
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            Tag::String(s) => s.clone(),
+            Tag::Byte(b) if *b == 0 || *b == 1 => (*b == 1).to_string(),
+            Tag::Float(v) => v.to_string(),
+            Tag::Double(v) => v.to_string(),
+            other => other.as_i64().map(|v| v.to_string()).unwrap_or_default(),
+        }
+    }
+
+    /// Converts to JSON for the frontend. Byte flags stay numbers since NBT has no booleans.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+        match self {
+            Tag::Byte(v) => Value::from(*v),
+            Tag::Short(v) => Value::from(*v),
+            Tag::Int(v) => Value::from(*v),
+            Tag::Long(v) => Value::from(*v),
+            Tag::Float(v) => Value::from(*v),
+            Tag::Double(v) => Value::from(*v),
+            Tag::String(s) => Value::from(s.as_str()),
+            Tag::ByteArray(v) => Value::from(v.clone()),
+            Tag::IntArray(v) => Value::from(v.clone()),
+            Tag::LongArray(v) => Value::from(v.clone()),
+            Tag::List(_, items) => Value::Array(items.iter().map(|t| t.to_json()).collect()),
+            Tag::Compound(c) => Value::Object(c.0.iter().map(|(k, v)| (k.clone(), v.to_json())).collect()),
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(n).filter(|e| *e <= self.data.len()).ok_or("Unexpected end of NBT data")?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> { Ok(self.take(1)?[0]) }
+    fn i16(&mut self) -> Result<i16, String> { Ok(i16::from_be_bytes(self.take(2)?.try_into().unwrap())) }
+    fn i32(&mut self) -> Result<i32, String> { Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap())) }
+    fn i64(&mut self) -> Result<i64, String> { Ok(i64::from_be_bytes(self.take(8)?.try_into().unwrap())) }
+
+    fn len(&mut self) -> Result<usize, String> {
+        let len = self.i32()?;
+        if len < 0 { return Err(format!("Negative NBT length {}", len)); }
+        Ok(len as usize)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.i16()? as u16 as usize;
+        Ok(decode_mutf8(self.take(len)?))
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> Result<Tag, String> {
+        if depth > 512 { return Err("NBT nesting too deep".into()); }
+        Ok(match id {
+            1 => Tag::Byte(self.u8()? as i8),
+            2 => Tag::Short(self.i16()?),
+            3 => Tag::Int(self.i32()?),
+            4 => Tag::Long(self.i64()?),
+            5 => Tag::Float(f32::from_bits(self.i32()? as u32)),
+            6 => Tag::Double(f64::from_bits(self.i64()? as u64)),
+            7 => { let len = self.len()?; Tag::ByteArray(self.take(len)?.iter().map(|b| *b as i8).collect()) }
+            8 => Tag::String(self.string()?),
+            9 => {
+                let item_id = self.u8()?;
+                let len = self.len()?;
+                let mut items = Vec::with_capacity(len.min(4096));
+                for _ in 0..len { items.push(self.payload(item_id, depth + 1)?); }
+                Tag::List(item_id, items)
+            }
+            10 => {
+                let mut compound = Compound::default();
+                loop {
+                    let child_id = self.u8()?;
+                    if child_id == 0 { break; }
+                    let name = self.string()?;
+                    compound.0.push((name, self.payload(child_id, depth + 1)?));
+                }
+                Tag::Compound(compound)
+            }
+            11 => { let len = self.len()?; Tag::IntArray((0..len).map(|_| self.i32()).collect::<Result<_, _>>()?) }
+            12 => { let len = self.len()?; Tag::LongArray((0..len).map(|_| self.i64()).collect::<Result<_, _>>()?) }
+            other => return Err(format!("Unknown NBT tag type {}", other)),
+        })
+    }
+}
+
+/// Parses uncompressed NBT with a named root compound, returning the root name and contents.
+pub fn from_bytes(data: &[u8]) -> Result<(String, Compound), String> {
+    let mut reader = Reader { data, pos: 0 };
+    if reader.u8()? != 10 { return Err("NBT root is not a compound".into()); }
+    let name = reader.string()?;
+    match reader.payload(10, 0)? {
+        Tag::Compound(c) => Ok((name, c)),
+        _ => unreachable!(),
+    }
+}
+
+pub fn to_bytes(name: &str, root: &Compound) -> Vec<u8> {
+    let mut out = vec![10];
+    write_string(&mut out, name);
+    write_payload(&mut out, &Tag::Compound(root.clone()));
+    out
+}
+
+/// Reads NBT that may be gzip-compressed (`level.dat`, playerdata) or raw.
+pub fn read_file(path: &Path) -> Result<(String, Compound), String> {
+    let raw = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    decompress(&raw).and_then(|data| from_bytes(&data)).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Detects gzip/zlib by magic bytes and inflates accordingly.
+pub fn decompress(raw: &[u8]) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    if raw.starts_with(&[0x1f, 0x8b]) {
+        GzDecoder::new(raw).read_to_end(&mut data).map_err(|e| e.to_string())?;
+    } else if raw.first() == Some(&0x78) {
+        ZlibDecoder::new(raw).read_to_end(&mut data).map_err(|e| e.to_string())?;
+    } else {
+        data = raw.to_vec();
+    }
+    Ok(data)
+}
+
+/// Writes gzip-compressed NBT, keeping the previous file as `<name>_old` like the game does.
+pub fn write_file(path: &Path, name: &str, root: &Compound) -> Result<(), String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&to_bytes(name, root)).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+    let file_name = path.file_name().ok_or("Invalid NBT path")?.to_string_lossy();
+    let tmp = path.with_file_name(format!("{}.tmp", file_name));
+    fs::write(&tmp, compressed).map_err(|e| e.to_string())?;
+    if path.exists() {
+        fs::copy(path, path.with_file_name(format!("{}_old", file_name))).map_err(|e| e.to_string())?;
+    }
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    let bytes = encode_mutf8(s);
+    out.extend_from_slice(&(bytes.len().min(u16::MAX as usize) as u16).to_be_bytes());
+    out.extend_from_slice(&bytes[..bytes.len().min(u16::MAX as usize)]);
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(v) => out.push(*v as u8),
+        Tag::Short(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Long(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Double(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::ByteArray(v) => {
+            out.extend_from_slice(&(v.len() as i32).to_be_bytes());
+            out.extend(v.iter().map(|b| *b as u8));
+        }
+        Tag::String(s) => write_string(out, s),
+        Tag::List(item_id, items) => {
+            out.push(items.first().map_or(*item_id, |t| t.id()));
+            out.extend_from_slice(&(items.len() as i32).to_be_bytes());
+            for item in items { write_payload(out, item); }
+        }
+        Tag::Compound(c) => {
+            for (name, value) in &c.0 {
+                out.push(value.id());
+                write_string(out, name);
+                write_payload(out, value);
+            }
+            out.push(0);
+        }
+        Tag::IntArray(v) => {
+            out.extend_from_slice(&(v.len() as i32).to_be_bytes());
+            for i in v { out.extend_from_slice(&i.to_be_bytes()); }
+        }
+        Tag::LongArray(v) => {
+            out.extend_from_slice(&(v.len() as i32).to_be_bytes());
+            for i in v { out.extend_from_slice(&i.to_be_bytes()); }
+        }
+    }
+}
+
+/// Java's "modified UTF-8": NUL is two bytes and supplementary characters are surrogate pairs.
+fn decode_mutf8(bytes: &[u8]) -> String {
+    if let Ok(s) = std::str::from_utf8(bytes) { if !s.contains('\0') { return s.to_string(); } }
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i] as u16;
+        if b < 0x80 {
+            units.push(b);
+            i += 1;
+        } else if b & 0xE0 == 0xC0 && i + 1 < bytes.len() {
+            units.push(((b & 0x1F) << 6) | (bytes[i + 1] as u16 & 0x3F));
+            i += 2;
+        } else if b & 0xF0 == 0xE0 && i + 2 < bytes.len() {
+            units.push(((b & 0x0F) << 12) | ((bytes[i + 1] as u16 & 0x3F) << 6) | (bytes[i + 2] as u16 & 0x3F));
+            i += 3;
+        } else {
+            units.push(0xFFFD);
+            i += 1;
+        }
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn encode_mutf8(s: &str) -> Vec<u8> {
+    if !s.contains('\0') && s.chars().all(|c| (c as u32) < 0x10000) { return s.as_bytes().to_vec(); }
+    let mut out = Vec::with_capacity(s.len() + 8);
+    for unit in s.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => out.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                out.push(0xC0 | (unit >> 6) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                out.push(0xE0 | (unit >> 12) as u8);
+                out.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+    out
+}
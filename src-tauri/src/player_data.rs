@@ -0,0 +1,171 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+use crate::nbt::{self, Compound, Tag};
+use crate::{datapacks, profiles, worlds, AppState};
+
+#[derive(Serialize, Debug)]
+pub struct Enchantment {
+    pub id: String,
+    pub level: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ItemStack {
+    /// Inventory index (`0`-`8` hotbar, `9`-`35` main), an equipment slot such as `head` or
+    /// `offhand`, or the ender chest index.
+    pub slot: String,
+    pub id: String,
+    pub count: i64,
+    pub damage: Option<i64>,
+    pub custom_name: Option<String>,
+    pub enchantments: Vec<Enchantment>,
+    /// Enchantments stored in enchanted books.
+    pub stored_enchantments: Vec<Enchantment>,
+    /// The item's remaining data (`components` since 1.20.5, `tag` before) for anything not listed above.
+    pub data: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlayerData {
+    pub uuid: String,
+    pub name: String,
+    pub data_version: Option<i64>,
+    /// When the server last wrote the file; online players are only saved periodically.
+    pub last_saved: Option<String>,
+    pub dimension: String,
+    pub position: Option<[f64; 3]>,
+    pub health: Option<f64>,
+    pub food_level: Option<i64>,
+    pub xp_level: Option<i64>,
+    pub xp_progress: Option<f64>,
+    pub xp_total: Option<i64>,
+    pub game_mode: Option<String>,
+    pub selected_slot: Option<i64>,
+    pub inventory: Vec<ItemStack>,
+    pub armor: Vec<ItemStack>,
+    pub offhand: Option<ItemStack>,
+    pub ender_chest: Vec<ItemStack>,
+}
+
+/// Reads enchantments from the 1.20.5+ component (`{levels: {id: lvl}}`, flattened in 1.21.5)
+/// or the older `[{id, lvl}]` list.
+fn enchantments(tag: Option<&Tag>) -> Vec<Enchantment> {
+    match tag {
+        Some(Tag::Compound(c)) => {
+            let levels = c.compound("levels").unwrap_or(c);
+            levels.0.iter().filter_map(|(id, lvl)| Some(Enchantment { id: id.clone(), level: lvl.as_i64()? })).collect()
+        }
+        Some(Tag::List(_, items)) => items.iter().filter_map(|e| {
+            let e = e.as_compound()?;
+            let id = match e.get("id")? { Tag::String(s) => s.clone(), other => other.as_i64()?.to_string() };
+            Some(Enchantment { id, level: e.get("lvl")?.as_i64()? })
+        }).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Item names are JSON text, or an NBT text component since 1.21.5.
+fn text(tag: &Tag) -> String {
+    match tag {
+        Tag::String(s) => serde_json::from_str(s).map(|v| datapacks::component_text(&v)).unwrap_or_else(|_| s.clone()),
+        other => datapacks::component_text(&other.to_json()),
+    }
+}
+
+fn item_stack(slot: String, item: &Compound) -> Option<ItemStack> {
+    let id = item.get("id")?.as_str()?.to_string();
+    let count = item.get("count").or_else(|| item.get("Count")).and_then(|t| t.as_i64()).unwrap_or(1);
+    let (components, legacy) = (item.compound("components"), item.compound("tag"));
+    let component = |key: &str| components.and_then(|c| c.get(key));
+    Some(ItemStack {
+        slot,
+        id,
+        count,
+        damage: component("minecraft:damage").or_else(|| legacy?.get("Damage")).and_then(|t| t.as_i64()),
+        custom_name: component("minecraft:custom_name").or_else(|| legacy?.path("display.Name")).map(text),
+        enchantments: enchantments(component("minecraft:enchantments").or_else(|| legacy?.get("Enchantments"))),
+        stored_enchantments: enchantments(component("minecraft:stored_enchantments").or_else(|| legacy?.get("StoredEnchantments"))),
+        data: components.or(legacy).map(|c| Tag::Compound(c.clone()).to_json()),
+    })
+}
+
+fn slotted_items(list: Option<&Tag>) -> Vec<(i64, &Compound)> {
+    list.and_then(|t| t.as_list()).map(|items| items.iter()
+        .filter_map(|t| t.as_compound())
+        .filter_map(|c| Some((c.get("Slot")?.as_i64()?, c)))
+        .collect()).unwrap_or_default()
+}
+
+fn dimension_name(tag: Option<&Tag>) -> String {
+    match tag {
+        Some(Tag::String(s)) => s.clone(),
+        // Before 1.16 the dimension was stored as a number
+        Some(other) => match other.as_i64() {
+            Some(-1) => "minecraft:the_nether".into(),
+            Some(1) => "minecraft:the_end".into(),
+            _ => "minecraft:overworld".into(),
+        },
+        None => "minecraft:overworld".into(),
+    }
+}
+
+/// Reads a player's saved state from `<world>/playerdata/<uuid>.dat` in the active world.
+#[tauri::command]
+pub async fn get_player_data(server_path: String, uuid: String, state: State<'_, AppState>) -> Result<PlayerData, String> {
+    crate::validate_folder_name(&uuid)?;
+    let path = Path::new(&server_path).join(worlds::active_world_name(&server_path)).join("playerdata").join(format!("{}.dat", uuid));
+    if !path.exists() { return Err(format!("No player data saved for {}", uuid)); }
+    let (_, root) = nbt::read_file(&path)?;
+    let last_saved = std::fs::metadata(&path).and_then(|m| m.modified()).ok()
+        .map(|t| chrono::DateTime::<chrono::Local>::from(t).format("%Y-%m-%d %H:%M:%S").to_string());
+
+    let mut inventory = Vec::new();
+    let mut armor = Vec::new();
+    let mut offhand = None;
+    for (slot, item) in slotted_items(root.get("Inventory")) {
+        // Armor and offhand lived in the inventory list until 1.21.5
+        let name = match slot {
+            100 => "feet", 101 => "legs", 102 => "chest", 103 => "head", -106 => "offhand",
+            _ => { inventory.extend(item_stack(slot.to_string(), item)); continue; }
+        };
+        let stack = item_stack(name.to_string(), item);
+        if slot == -106 { offhand = stack; } else { armor.extend(stack); }
+    }
+    if let Some(equipment) = root.compound("equipment") {
+        for name in ["head", "chest", "legs", "feet", "body"] {
+            armor.extend(equipment.compound(name).and_then(|item| item_stack(name.to_string(), item)));
+        }
+        if let Some(item) = equipment.compound("offhand") { offhand = item_stack("offhand".into(), item); }
+    }
+    let ender_chest = slotted_items(root.get("EnderItems")).into_iter()
+        .filter_map(|(slot, item)| item_stack(slot.to_string(), item)).collect();
+
+    let position = root.get("Pos").and_then(|t| t.as_list())
+        .and_then(|p| Some([p.first()?.as_f64()?, p.get(1)?.as_f64()?, p.get(2)?.as_f64()?]));
+    let names = profiles::names_for_uuids(&state, &server_path, std::slice::from_ref(&uuid)).await;
+    // Bukkit records the last name a player used, which helps when usercache has expired
+    let name = names.get(&uuid).cloned()
+        .or_else(|| root.path("bukkit.lastKnownName").and_then(|t| t.as_str()).map(String::from))
+        .unwrap_or_else(|| uuid.clone());
+    Ok(PlayerData {
+        uuid,
+        name,
+        data_version: root.get("DataVersion").and_then(|t| t.as_i64()),
+        last_saved,
+        dimension: dimension_name(root.get("Dimension")),
+        position,
+        health: root.get("Health").and_then(|t| t.as_f64()),
+        food_level: root.get("foodLevel").and_then(|t| t.as_i64()),
+        xp_level: root.get("XpLevel").and_then(|t| t.as_i64()),
+        xp_progress: root.get("XpP").and_then(|t| t.as_f64()),
+        xp_total: root.get("XpTotal").and_then(|t| t.as_i64()),
+        game_mode: root.get("playerGameType").and_then(|t| t.as_i64()).map(|id| worlds::game_mode_name(id).to_string()),
+        selected_slot: root.get("SelectedItemSlot").and_then(|t| t.as_i64()),
+        inventory,
+        armor,
+        offhand,
+        ender_chest,
+    })
+}
@@ -0,0 +1,166 @@
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::rcon::RconClient;
+use crate::{find_orphaned_java_process, is_managed_server, sessions, AppState};
+
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(60);
+/// The id servers give "Anonymous Player" entries for players who hide from the server list.
+const NIL_UUID: &str = "00000000-0000-0000-0000-000000000000";
+
+pub struct StatusPing {
+    pub online: usize,
+    /// Vanilla only includes up to 12 names and leaves out anonymous players, so this can be
+    /// partial or empty.
+    pub sample: Vec<String>,
+}
+
+/// How many console lines after a bare `list` header may carry the names before giving up.
+const NAME_LINE_WINDOW: usize = 5;
+
+/// Tracks `list` output in the console. Old Spigot builds print the names on the following line.
+#[derive(Default)]
+pub struct ListParser {
+    lines_left: usize,
+}
+
+impl ListParser {
+    /// Returns the full set of online players when the line completes a `list` response.
+    pub fn feed(&mut self, line: &str) -> Option<BTreeSet<String>> {
+        let message = sessions::console_message(line)?;
+        if self.lines_left > 0 {
+            // Other output can arrive in between, so only a line made entirely of names counts
+            self.lines_left -= 1;
+            if message.split(',').map(str::trim).all(valid_name) {
+                self.lines_left = 0;
+                return Some(names(message));
+            }
+        }
+        let (count, rest) = parse_list_header(message)?;
+        if rest.is_empty() && count > 0 {
+            self.lines_left = NAME_LINE_WINDOW;
+            return None;
+        }
+        Some(names(rest))
+    }
+}
+
+/// Parses "There are 2 of a max of 20 players online: a, b" (or "There are 2/20 players online:").
+fn parse_list_header(message: &str) -> Option<(usize, &str)> {
+    let rest = message.strip_prefix("There are ")?;
+    let (_, names) = rest.split_once("players online:")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    Some((digits.parse().ok()?, names.trim()))
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 16 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn names(list: &str) -> BTreeSet<String> {
+    list.split(',').map(str::trim).filter(|n| valid_name(n)).map(String::from).collect()
+}
+
+/// Applies `change` to the online set and emits `player-update` with the names if it changed.
+pub(crate) fn update_online(app: &AppHandle, online: &Mutex<BTreeSet<String>>, change: impl FnOnce(&mut BTreeSet<String>)) {
+    let mut players = online.lock().unwrap();
+    let before = players.clone();
+    change(&mut players);
+    if *players != before { app.emit("player-update", players.iter().cloned().collect::<Vec<_>>()).unwrap(); }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        if value & !0x7F == 0 { out.push(value as u8); return; }
+        out.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+}
+
+fn read_varint(stream: &mut impl Read) -> Result<u32, String> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let mut byte = [0u8];
+        stream.read_exact(&mut byte).map_err(|e| e.to_string())?;
+        value |= ((byte[0] & 0x7F) as u32) << shift;
+        if byte[0] & 0x80 == 0 { return Ok(value); }
+    }
+    Err("VarInt too long".into())
+}
+
+/// Server List Ping against the local server, the same request the multiplayer screen sends.
+pub fn ping(port: u16) -> Result<StatusPing, String> {
+    let mut stream = TcpStream::connect_timeout(&([127, 0, 0, 1], port).into(), Duration::from_secs(3)).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(Duration::from_secs(3))).map_err(|e| e.to_string())?;
+
+    let mut handshake = vec![0x00];
+    write_varint(&mut handshake, u32::MAX); // Protocol -1: "just checking status"
+    write_varint(&mut handshake, 9);
+    handshake.extend_from_slice(b"127.0.0.1");
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1);
+    let mut packet = Vec::new();
+    write_varint(&mut packet, handshake.len() as u32);
+    packet.extend(handshake);
+    packet.extend_from_slice(&[0x01, 0x00]);
+    stream.write_all(&packet).map_err(|e| e.to_string())?;
+
+    read_varint(&mut stream)?;
+    if read_varint(&mut stream)? != 0 { return Err("Unexpected status response".into()); }
+    let length = read_varint(&mut stream)? as usize;
+    let mut json = vec![0u8; length];
+    stream.read_exact(&mut json).map_err(|e| e.to_string())?;
+    let status: Value = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+    let players = &status["players"];
+    Ok(StatusPing {
+        online: players["online"].as_u64().unwrap_or(0) as usize,
+        sample: players["sample"].as_array().map(|s| s.iter()
+            .filter(|p| p["id"].as_str() != Some(NIL_UUID))
+            .filter_map(|p| p["name"].as_str().filter(|n| valid_name(n)).map(String::from))
+            .collect()).unwrap_or_default(),
+    })
+}
+
+/// Re-reads the online list when the status ping disagrees with what the console told us:
+/// via `list` on stdin for managed servers, RCON for adopted ones, or the ping sample as a last resort.
+fn reconcile(app: &AppHandle, state: &AppState, server_path: &str) {
+    let managed = is_managed_server(state, server_path);
+    if !managed && find_orphaned_java_process(server_path).is_none() {
+        update_online(app, &state.online_players, BTreeSet::clear);
+        return;
+    }
+    let port = crate::load_properties(server_path).get("server-port").and_then(|p| p.parse().ok()).unwrap_or(25565);
+    let Ok(status) = ping(port) else { return; };
+    {
+        let online = state.online_players.lock().unwrap();
+        // The names in the sample must all be known; the count covers anyone hidden or cut off
+        if online.len() == status.online && status.sample.iter().all(|n| online.contains(n)) { return; }
+    }
+    if managed {
+        // The log reader picks up the response
+        let _ = crate::write_to_stdin(state, "list");
+    } else if let Some(Ok(mut client)) = RconClient::from_properties(server_path) {
+        if let Ok(out) = client.command("list") {
+            let out = out.replace('\n', " ");
+            if let Some((_, list)) = parse_list_header(out.trim()) { update_online(app, &state.online_players, |set| *set = names(list)); }
+        }
+    } else if status.sample.len() == status.online {
+        // Only a complete sample can replace the set; a hidden or partial one would drop players
+        update_online(app, &state.online_players, |set| *set = status.sample.into_iter().collect());
+    }
+}
+
+/// Starts the background thread that keeps the online player set honest.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(RECONCILE_INTERVAL);
+        let state = app.state::<AppState>();
+        let path = state.config.lock().unwrap().as_ref().map(|c| c.path.clone());
+        if let Some(path) = path { reconcile(&app, &state, &path); }
+    });
+}
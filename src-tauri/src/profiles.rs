@@ -0,0 +1,180 @@
+use futures_util::stream::{self, StreamExt};
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::AppState;
+
+const DEFAULT_PROFILE_API: &str = "https://api.minecraftservices.com/minecraft/profile/lookup";
+/// Cached lookups are trusted for a week since players can change their names.
+const CACHE_DAYS: i64 = 7;
+/// Lookups that failed or found nothing are not retried for this long.
+const FAILURE_TTL: Duration = Duration::from_secs(60 * 60);
+const CONCURRENT_LOOKUPS: usize = 8;
+
+/// Lookup URLs that recently failed, with when they did.
+static FAILED_LOOKUPS: Mutex<BTreeMap<String, Instant>> = Mutex::new(BTreeMap::new());
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Profile {
+    pub uuid: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ResolvedProfile {
+    pub uuid: String,
+    pub name: String,
+    /// `usercache`, `offline`, `cache` or `api`.
+    pub source: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CachedProfile {
+    uuid: String,
+    name: String,
+    cached_at: i64,
+}
+
+#[derive(Deserialize)]
+struct ApiProfile {
+    id: String,
+    name: String,
+}
+
+/// The UUID an offline-mode server assigns: a v3 UUID of the MD5 of `OfflinePlayer:<name>`.
+pub fn offline_uuid(name: &str) -> String {
+    let mut bytes: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", name).as_bytes()).into();
+    bytes[6] = (bytes[6] & 0x0f) | 0x30;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    hyphenate(&bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+fn hyphenate(hex: &str) -> String {
+    if hex.len() != 32 { return hex.to_string(); }
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+fn cache_path(server_path: &str) -> PathBuf {
+    Path::new(server_path).join("roam_profiles.json")
+}
+
+fn read_usercache(server_path: &str) -> Vec<Profile> {
+    fs::read_to_string(Path::new(server_path).join("usercache.json")).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Profiles fetched from the API that have not expired yet.
+fn read_cache(server_path: &str) -> Vec<CachedProfile> {
+    let cutoff = chrono::Utc::now().timestamp() - CACHE_DAYS * 86400;
+    fs::read_to_string(cache_path(server_path)).ok()
+        .and_then(|content| serde_json::from_str::<Vec<CachedProfile>>(&content).ok())
+        .map(|entries| entries.into_iter().filter(|e| e.cached_at > cutoff).collect())
+        .unwrap_or_default()
+}
+
+fn store_in_cache(server_path: &str, profiles: &[Profile]) {
+    if profiles.is_empty() { return; }
+    let mut entries = read_cache(server_path);
+    entries.retain(|e| !profiles.iter().any(|p| p.uuid == e.uuid));
+    let cached_at = chrono::Utc::now().timestamp();
+    entries.extend(profiles.iter().map(|p| CachedProfile { uuid: p.uuid.clone(), name: p.name.clone(), cached_at }));
+    if let Ok(json) = serde_json::to_string_pretty(&entries) { let _ = fs::write(cache_path(server_path), json); }
+}
+
+fn online_mode(server_path: &str) -> bool {
+    crate::load_properties(server_path).get("online-mode").is_none_or(|v| v != "false")
+}
+
+/// The profile API configured for this server, falling back to Mojang's.
+fn profile_api(state: &AppState, server_path: &str) -> String {
+    state.config.lock().unwrap().as_ref().filter(|c| c.path == server_path)
+        .and_then(|c| c.profile_api_url.clone()).filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE_API.into())
+        .trim_end_matches('/').to_string()
+}
+
+/// Whether player lists may ask the profile API for names (`ServerConfig::profile_lookups`).
+fn lookups_enabled(state: &AppState, server_path: &str) -> bool {
+    state.config.lock().unwrap().as_ref().filter(|c| c.path == server_path).is_some_and(|c| c.profile_lookups)
+}
+
+async fn fetch_profile(url: &str) -> Result<Option<Profile>, String> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build().map_err(|e| e.to_string())?;
+    let response = client.get(url).send().await.map_err(|e| format!("Profile lookup failed: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND || response.status() == reqwest::StatusCode::NO_CONTENT { return Ok(None); }
+    if !response.status().is_success() { return Err(format!("Profile lookup failed: HTTP {}", response.status())); }
+    let profile: ApiProfile = response.json().await.map_err(|e| format!("Unexpected profile response: {}", e))?;
+    Ok(Some(Profile { uuid: hyphenate(&profile.id.replace('-', "").to_lowercase()), name: profile.name }))
+}
+
+/// Finds a player's UUID: `usercache.json` first, then the offline-mode UUID or, on online-mode
+/// servers, the lookup cache and the profile API.
+pub(crate) async fn resolve_name(state: &AppState, server_path: &str, name: &str) -> Result<ResolvedProfile, String> {
+    let found = |uuid: &str, name: &str, source: &str| ResolvedProfile { uuid: uuid.to_string(), name: name.to_string(), source: source.to_string() };
+    if let Some(p) = read_usercache(server_path).iter().find(|p| p.name.eq_ignore_ascii_case(name)) {
+        return Ok(found(&p.uuid, &p.name, "usercache"));
+    }
+    if !online_mode(server_path) { return Ok(found(&offline_uuid(name), name, "offline")); }
+    if let Some(p) = read_cache(server_path).iter().find(|p| p.name.eq_ignore_ascii_case(name)) {
+        return Ok(found(&p.uuid, &p.name, "cache"));
+    }
+    let url = format!("{}/name/{}", profile_api(state, server_path), name);
+    let profile = fetch_profile(&url).await?.ok_or_else(|| format!("No Minecraft account is named '{}'", name))?;
+    store_in_cache(server_path, std::slice::from_ref(&profile));
+    Ok(found(&profile.uuid, &profile.name, "api"))
+}
+
+/// Maps UUIDs to names from `usercache.json` and the lookup cache. When profile lookups are
+/// enabled on an online-mode server, the rest are fetched from the profile API a few at a time.
+/// Failures just leave a UUID out of the map and are not retried until `FAILURE_TTL` passes.
+pub(crate) async fn names_for_uuids(state: &AppState, server_path: &str, uuids: &[String]) -> HashMap<String, String> {
+    let mut names: HashMap<String, String> = read_usercache(server_path).into_iter().map(|p| (p.uuid, p.name)).collect();
+    for p in read_cache(server_path) { names.entry(p.uuid).or_insert(p.name); }
+    if !online_mode(server_path) || !lookups_enabled(state, server_path) { return names; }
+
+    let api = profile_api(state, server_path);
+    let urls: Vec<(String, String)> = {
+        let mut failed = FAILED_LOOKUPS.lock().unwrap();
+        failed.retain(|_, at| at.elapsed() < FAILURE_TTL);
+        // Offline-mode (v3) UUIDs are hashes and have no profile to look up
+        uuids.iter().filter(|u| !names.contains_key(*u) && u.chars().nth(14) == Some('4'))
+            .map(|u| (u.clone(), format!("{}/{}", api, u.replace('-', ""))))
+            .filter(|(_, url)| !failed.contains_key(url))
+            .collect()
+    };
+    let results: Vec<(String, String, Option<Profile>)> = stream::iter(urls)
+        .map(|(uuid, url)| async move {
+            let profile = fetch_profile(&url).await.ok().flatten();
+            (uuid, url, profile)
+        })
+        .buffer_unordered(CONCURRENT_LOOKUPS)
+        .collect().await;
+
+    let mut fetched = Vec::new();
+    let mut failed = FAILED_LOOKUPS.lock().unwrap();
+    for (uuid, url, profile) in results {
+        match profile {
+            Some(profile) => {
+                names.insert(uuid, profile.name.clone());
+                fetched.push(profile);
+            }
+            None => { failed.insert(url, Instant::now()); }
+        }
+    }
+    drop(failed);
+    store_in_cache(server_path, &fetched);
+    names
+}
+
+#[tauri::command]
+pub async fn resolve_player_uuid(server_path: String, name: String, state: State<'_, AppState>) -> Result<ResolvedProfile, String> {
+    crate::access_lists::validate_player_name(&name)?;
+    resolve_name(&state, &server_path, &name).await
+}
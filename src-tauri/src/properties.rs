@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::fs;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+
+use crate::property_schema;
+
+/// One logical line of a `.properties` file. Entries keep their original text so untouched
+/// lines are written back byte for byte.
+enum Line {
+    /// Comments, blank lines and anything else that is not a key/value pair.
+    Other(String),
+    Entry { key: String, value: String, raw: String },
+}
+
+/// A `.properties` file that round-trips comments, key order, escapes and line continuations.
+pub struct Properties {
+    lines: Vec<Line>,
+    newline: &'static str,
+}
+
+fn properties_path(server_path: &str) -> PathBuf {
+    Path::new(server_path).join("server.properties")
+}
+
+/// Splits text into logical lines: a line ending in an odd number of backslashes continues on the
+/// next one. Comment lines never continue.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current: Option<String> = None;
+    if content.is_empty() { return lines; }
+    for physical in content.strip_suffix('\n').unwrap_or(content).split('\n').map(|l| l.strip_suffix('\r').unwrap_or(l)) {
+        let mut line = match current.take() {
+            Some(mut pending) => { pending.push('\n'); pending.push_str(physical); pending }
+            None => {
+                let trimmed = physical.trim_start();
+                if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') { lines.push(physical.to_string()); continue; }
+                physical.to_string()
+            }
+        };
+        let trailing = physical.chars().rev().take_while(|c| *c == '\\').count();
+        if trailing % 2 == 1 { current = Some(std::mem::take(&mut line)); } else { lines.push(line); }
+    }
+    // A continuation on the very last line just ends the value
+    if let Some(pending) = current { lines.push(pending); }
+    lines
+}
+
+/// Reads the four hex digits of a `\uXXXX` escape, returning what was consumed and its value.
+fn hex_unit(chars: &mut Peekable<Chars>) -> (String, Option<u32>) {
+    let hex: String = (0..4).filter_map(|_| chars.next_if(char::is_ascii_hexdigit)).collect();
+    let unit = u32::from_str_radix(&hex, 16).ok().filter(|_| hex.len() == 4);
+    (hex, unit)
+}
+
+/// Decodes Java escapes (`\t`, `\n`, `\r`, `\f`, `\uXXXX`, `\x` for anything else) and joins
+/// continuations, dropping the leading whitespace of each continued line.
+fn unescape(raw: &str) -> String {
+    let mut out = String::new();
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' { out.push(c); continue; }
+        match chars.next() {
+            Some('\n') => { while chars.next_if(|c| matches!(c, ' ' | '\t' | '\x0c')).is_some() {} }
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('f') => out.push('\x0c'),
+            Some('u') => {
+                let (hex, mut unit) = hex_unit(&mut chars);
+                // Characters outside the BMP are written as a high and a low surrogate escape
+                if let Some(high @ 0xD800..=0xDBFF) = unit {
+                    let mut ahead = chars.clone();
+                    if ahead.next() == Some('\\') && ahead.next() == Some('u') {
+                        if let (_, Some(low @ 0xDC00..=0xDFFF)) = hex_unit(&mut ahead) {
+                            unit = Some(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00));
+                            chars = ahead;
+                        }
+                    }
+                }
+                match unit.and_then(char::from_u32) {
+                    Some(decoded) => out.push(decoded),
+                    None => { out.push('u'); out.push_str(&hex); }
+                }
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// The key ends at the first unescaped `=`, `:` or whitespace; one separator and the whitespace
+/// around it is skipped before the value.
+fn parse_entry(line: &str) -> (String, String) {
+    let text = line.trim_start_matches([' ', '\t', '\x0c']);
+    let mut key_end = text.len();
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped { escaped = false; continue; }
+        if c == '\\' { escaped = true; continue; }
+        if matches!(c, '=' | ':' | ' ' | '\t' | '\x0c') { key_end = i; break; }
+    }
+    let mut rest = text[key_end..].trim_start_matches([' ', '\t', '\x0c']);
+    if let Some(after) = rest.strip_prefix(['=', ':']) { rest = after.trim_start_matches([' ', '\t', '\x0c']); }
+    (unescape(&text[..key_end]), unescape(rest))
+}
+
+/// Escapes like `Properties.store`, which is what the server itself writes. Non-ASCII is written
+/// as `\uXXXX` so the file reads the same as UTF-8 or ISO-8859-1.
+fn escape(text: &str, is_key: bool) -> String {
+    let mut out = String::new();
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            ' ' if is_key || i == 0 => out.push_str("\\ "),
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\x0c' => out.push_str("\\f"),
+            '=' | ':' | '#' | '!' => { out.push('\\'); out.push(c); }
+            c if (c as u32) < 0x20 || (c as u32) > 0x7e => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) { out.push_str(&format!("\\u{:04X}", unit)); }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl Properties {
+    pub fn parse(content: &str) -> Self {
+        let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let lines = logical_lines(content).into_iter().map(|raw| {
+            let trimmed = raw.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') { return Line::Other(raw); }
+            let (key, value) = parse_entry(&raw);
+            Line::Entry { key, value, raw }
+        }).collect();
+        Properties { lines, newline }
+    }
+
+    /// Reads `server.properties`, which the server writes as UTF-8 but older versions wrote as ISO-8859-1.
+    pub fn load(server_path: &str) -> Self {
+        let bytes = fs::read(properties_path(server_path)).unwrap_or_default();
+        let content = String::from_utf8(bytes).unwrap_or_else(|e| e.into_bytes().iter().map(|b| *b as char).collect());
+        Self::parse(&content)
+    }
+
+    pub fn save(&self, server_path: &str) -> Result<(), String> {
+        let path = properties_path(server_path);
+        let tmp = path.with_extension("properties.tmp");
+        fs::write(&tmp, self.to_string()).map_err(|e| e.to_string())?;
+        fs::rename(&tmp, path).map_err(|e| e.to_string())
+    }
+
+    /// Updates the key in place, or appends it. Lines whose value already matches are left as they were.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let raw = format!("{}={}", escape(key, true), escape(value, false));
+        let mut existing = self.lines.iter_mut().rev().filter_map(|line| match line {
+            Line::Entry { key: k, value: v, raw: r } if k == key => Some((v, r)),
+            _ => None,
+        });
+        match existing.next() {
+            Some((v, _)) if v == value => {}
+            Some((v, r)) => { *v = value.to_string(); *r = raw; }
+            None => self.lines.push(Line::Entry { key: key.to_string(), value: value.to_string(), raw }),
+        }
+    }
+
+    /// Removes every definition of the key.
+    pub fn remove(&mut self, key: &str) {
+        self.lines.retain(|line| !matches!(line, Line::Entry { key: k, .. } if k == key));
+    }
+
+    pub fn to_map(&self) -> HashMap<String, String> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Entry { key, value, .. } => Some((key.clone(), value.clone())),
+            Line::Other(_) => None,
+        }).collect()
+    }
+}
+
+impl std::fmt::Display for Properties {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.lines {
+            let raw = match line { Line::Other(raw) | Line::Entry { raw, .. } => raw };
+            write!(f, "{}{}", raw.replace('\n', self.newline), self.newline)?;
+        }
+        Ok(())
+    }
+}
+
+/// Applies individual changes to `server.properties`, keeping comments, order and other keys intact.
+pub(crate) fn patch(server_path: &str, set: &HashMap<String, String>, remove: &[String]) -> Result<(), String> {
+    let mut props = Properties::load(server_path);
+    for key in remove { props.remove(key); }
+    let mut keys: Vec<&String> = set.keys().collect();
+    keys.sort();
+    for key in keys { props.set(key, &set[key]); }
+    props.save(server_path)
+}
+
+/// Sets and removes individual keys, leaving the rest of the file (and other tools' edits) untouched.
+#[tauri::command]
+pub async fn patch_properties(path: String, set: HashMap<String, String>, remove: Option<Vec<String>>) -> Result<(), String> {
+    property_schema::validate(&set)?;
+    patch(&path, &set, &remove.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(content: &str) {
+        assert_eq!(Properties::parse(content).to_string(), content);
+    }
+
+    #[test]
+    fn keeps_comments_and_order() {
+        let content = "#Minecraft server properties\n#Mon Jan 01 00:00:00 UTC 2024\n\nmotd=A Minecraft Server\n! bang comment\nlevel-name=world\n";
+        round_trip(content);
+        let mut props = Properties::parse(content);
+        props.set("motd", "Hello");
+        assert_eq!(props.to_string(), content.replace("A Minecraft Server", "Hello"));
+    }
+
+    #[test]
+    fn joins_continuations() {
+        let content = "motd=first \\\n    second\nlevel-name=world\n";
+        round_trip(content);
+        let map = Properties::parse(content).to_map();
+        assert_eq!(map["motd"], "first second");
+        assert_eq!(map["level-name"], "world");
+    }
+
+    #[test]
+    fn unescapes_separators_in_keys_and_values() {
+        let content = "a\\:b=c\\=d\nkey\\=x:value\\:y\n";
+        round_trip(content);
+        let map = Properties::parse(content).to_map();
+        assert_eq!(map["a:b"], "c=d");
+        assert_eq!(map["key=x"], "value:y");
+        let mut props = Properties::parse("");
+        props.set("motd", "a=b:c");
+        assert_eq!(props.to_string(), "motd=a\\=b\\:c\n");
+        assert_eq!(Properties::parse(&props.to_string()).to_map()["motd"], "a=b:c");
+    }
+
+    #[test]
+    fn decodes_unicode_escapes() {
+        let content = "motd=Caf\\u00E9 \\u00e9t\\u00E9\n";
+        round_trip(content);
+        assert_eq!(Properties::parse(content).to_map()["motd"], "Café été");
+        let mut props = Properties::parse("");
+        props.set("motd", "Café 🎉");
+        assert_eq!(props.to_string(), "motd=Caf\\u00E9 \\uD83C\\uDF89\n");
+        assert_eq!(Properties::parse(&props.to_string()).to_map()["motd"], "Café 🎉");
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let content = "#comment\r\nmotd=one \\\r\n  two\r\npvp=true\r\n";
+        round_trip(content);
+        let mut props = Properties::parse(content);
+        assert_eq!(props.to_map()["motd"], "one two");
+        props.set("pvp", "false");
+        props.set("difficulty", "hard");
+        assert_eq!(props.to_string(), "#comment\r\nmotd=one \\\r\n  two\r\npvp=false\r\ndifficulty=hard\r\n");
+    }
+}
@@ -0,0 +1,210 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::State;
+
+use crate::{worlds, AppState};
+
+const V1_12: i64 = 1139;
+const V1_13: i64 = 1519;
+const V1_14: i64 = 1952;
+const V1_16: i64 = 2566;
+const V1_17: i64 = 2724;
+const V1_18: i64 = 2860;
+const V1_19: i64 = 3105;
+const V1_19_3: i64 = 3218;
+const V1_20_2: i64 = 3578;
+const V1_20_3: i64 = 3698;
+const V1_20_5: i64 = 3837;
+const V1_21: i64 = 3953;
+const V1_21_2: i64 = 4080;
+const V1_21_9: i64 = 4554;
+
+const PORT: Kind = Kind::Integer(1, 65535);
+const NON_NEGATIVE: Kind = Kind::Integer(0, i32::MAX as i64);
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Boolean,
+    Integer(i64, i64),
+    Enum(&'static [&'static str]),
+    String,
+}
+
+/// When a change takes effect: the server only reads `server.properties` at startup, and some keys
+/// only matter when a world is generated.
+#[derive(Clone, Copy, PartialEq)]
+enum Applies {
+    Restart,
+    NewWorld,
+}
+
+struct Spec {
+    key: &'static str,
+    kind: Kind,
+    default: &'static str,
+    since: i64,
+    until: Option<i64>,
+    applies: Applies,
+    description: &'static str,
+}
+
+const fn spec(key: &'static str, kind: Kind, default: &'static str, since: i64, description: &'static str) -> Spec {
+    Spec { key, kind, default, since, until: None, applies: Applies::Restart, description }
+}
+
+impl Spec {
+    const fn until(self, until: i64) -> Spec { Spec { until: Some(until), ..self } }
+    const fn new_world(self) -> Spec { Spec { applies: Applies::NewWorld, ..self } }
+}
+
+/// Vanilla `server.properties` keys with the data version that added (and removed) them.
+const SCHEMA: &[Spec] = &[
+    spec("accepts-transfers", Kind::Boolean, "false", V1_20_5, "Accept players transferred here from another server."),
+    spec("allow-flight", Kind::Boolean, "false", V1_12, "Stop kicking players for flying in survival, e.g. with mods."),
+    spec("allow-nether", Kind::Boolean, "true", V1_12, "Let players travel to the Nether."),
+    spec("broadcast-console-to-ops", Kind::Boolean, "true", V1_14, "Show console command output to online operators."),
+    spec("broadcast-rcon-to-ops", Kind::Boolean, "true", V1_14, "Show RCON command output to online operators."),
+    spec("bug-report-link", Kind::String, "", V1_21, "Link shown to players on the disconnect screen for reporting bugs."),
+    spec("difficulty", Kind::Enum(&["peaceful", "easy", "normal", "hard"]), "easy", V1_12, "World difficulty."),
+    spec("enable-command-block", Kind::Boolean, "false", V1_12, "Allow command blocks to run."),
+    spec("enable-jmx-monitoring", Kind::Boolean, "false", V1_16, "Expose tick timings over JMX."),
+    spec("enable-query", Kind::Boolean, "false", V1_12, "Answer GameSpy4 query requests."),
+    spec("enable-rcon", Kind::Boolean, "false", V1_12, "Accept remote console connections."),
+    spec("enable-status", Kind::Boolean, "true", V1_16, "Show the server as online in the multiplayer list."),
+    spec("enforce-secure-profile", Kind::Boolean, "true", V1_19, "Require players to have a Mojang-signed chat key."),
+    spec("enforce-whitelist", Kind::Boolean, "false", V1_13, "Kick online players who are not whitelisted when the whitelist reloads."),
+    spec("entity-broadcast-range-percentage", Kind::Integer(10, 1000), "100", V1_16, "How far away entities are sent to clients, as a percentage of the default."),
+    spec("force-gamemode", Kind::Boolean, "false", V1_12, "Put players in the default game mode every time they join."),
+    spec("function-permission-level", Kind::Integer(1, 4), "2", V1_14, "Permission level of datapack functions."),
+    spec("gamemode", Kind::Enum(&["survival", "creative", "adventure", "spectator"]), "survival", V1_12, "Game mode for new players."),
+    spec("generate-structures", Kind::Boolean, "true", V1_12, "Generate villages, temples and other structures.").new_world(),
+    spec("generator-settings", Kind::String, "{}", V1_12, "Settings for the flat and customized world types.").new_world(),
+    spec("hardcore", Kind::Boolean, "false", V1_12, "Players become spectators when they die."),
+    spec("hide-online-players", Kind::Boolean, "false", V1_18, "Leave the player sample out of status responses."),
+    spec("initial-disabled-packs", Kind::String, "", V1_19_3, "Datapacks disabled when the world is created.").new_world(),
+    spec("initial-enabled-packs", Kind::String, "vanilla", V1_19_3, "Datapacks enabled when the world is created.").new_world(),
+    spec("level-name", Kind::String, "world", V1_12, "Folder of the world to load."),
+    spec("level-seed", Kind::String, "", V1_12, "Seed for a new world; random when empty.").new_world(),
+    spec("level-type", Kind::String, "minecraft:normal", V1_12, "World preset, e.g. minecraft:flat or minecraft:large_biomes.").new_world(),
+    spec("log-ips", Kind::Boolean, "true", V1_20_2, "Include player IP addresses in the log."),
+    spec("max-build-height", Kind::Integer(64, 256), "256", V1_12, "Highest block players can build at.").until(V1_17),
+    spec("max-chained-neighbor-updates", Kind::Integer(-1, i32::MAX as i64), "1000000", V1_19, "Limit on consecutive block updates before the rest are skipped; -1 for no limit."),
+    spec("max-players", NON_NEGATIVE, "20", V1_12, "Most players online at once."),
+    spec("max-tick-time", Kind::Integer(-1, i64::MAX), "60000", V1_14, "Milliseconds a tick may take before the watchdog stops the server; -1 to disable."),
+    spec("max-world-size", Kind::Integer(1, 29999984), "29999984", V1_12, "World border radius limit in blocks."),
+    spec("motd", Kind::String, "A Minecraft Server", V1_12, "Message shown in the multiplayer list."),
+    spec("network-compression-threshold", Kind::Integer(-1, i32::MAX as i64), "256", V1_12, "Packets larger than this many bytes are compressed; -1 to disable."),
+    spec("online-mode", Kind::Boolean, "true", V1_12, "Check players against Mojang's session servers."),
+    spec("op-permission-level", Kind::Integer(0, 4), "4", V1_12, "Permission level given by /op."),
+    spec("pause-when-empty-seconds", NON_NEGATIVE, "60", V1_21_2, "Seconds without players before the server stops ticking; 0 to never pause."),
+    spec("player-idle-timeout", NON_NEGATIVE, "0", V1_12, "Minutes before idle players are kicked; 0 to never kick."),
+    spec("prevent-proxy-connections", Kind::Boolean, "false", V1_12, "Kick players whose ISP differs from the one Mojang saw."),
+    spec("previews-chat", Kind::Boolean, "false", V1_19, "Let the server preview chat messages while they are typed.").until(V1_19_3),
+    spec("pvp", Kind::Boolean, "true", V1_12, "Let players damage each other.").until(V1_21_9),
+    spec("query.port", PORT, "25565", V1_12, "UDP port for query requests."),
+    spec("rate-limit", NON_NEGATIVE, "0", V1_12, "Packets per second a client may send before being kicked; 0 for no limit."),
+    spec("rcon.password", Kind::String, "", V1_12, "Password for remote console connections."),
+    spec("rcon.port", PORT, "25575", V1_12, "TCP port for remote console connections."),
+    spec("region-file-compression", Kind::Enum(&["deflate", "lz4", "none"]), "deflate", V1_20_5, "Compression for newly written chunks."),
+    spec("require-resource-pack", Kind::Boolean, "false", V1_17, "Kick players who decline the resource pack."),
+    spec("resource-pack", Kind::String, "", V1_12, "URL of the resource pack offered to players."),
+    spec("resource-pack-id", Kind::String, "", V1_20_3, "UUID identifying the resource pack on clients."),
+    spec("resource-pack-prompt", Kind::String, "", V1_17, "Message shown when offering the resource pack."),
+    spec("resource-pack-sha1", Kind::String, "", V1_12, "SHA-1 of the resource pack, so clients can cache it."),
+    spec("server-ip", Kind::String, "", V1_12, "Address to bind to; all interfaces when empty."),
+    spec("server-port", PORT, "25565", V1_12, "TCP port players connect to."),
+    spec("simulation-distance", Kind::Integer(3, 32), "10", V1_18, "Chunks around players in which entities are ticked."),
+    spec("snooper-enabled", Kind::Boolean, "true", V1_12, "Send usage data to Mojang.").until(V1_18),
+    spec("spawn-animals", Kind::Boolean, "true", V1_12, "Spawn animals.").until(V1_21_2),
+    spec("spawn-monsters", Kind::Boolean, "true", V1_12, "Spawn hostile mobs.").until(V1_21_9),
+    spec("spawn-npcs", Kind::Boolean, "true", V1_12, "Spawn villagers.").until(V1_21_2),
+    spec("spawn-protection", NON_NEGATIVE, "16", V1_12, "Radius around spawn that only operators can build in; 0 to disable."),
+    spec("sync-chunk-writes", Kind::Boolean, "true", V1_16, "Write region files synchronously."),
+    spec("text-filtering-config", Kind::String, "", V1_17, "Chat filtering service configuration."),
+    spec("use-native-transport", Kind::Boolean, "true", V1_12, "Use Linux epoll for networking."),
+    spec("view-distance", Kind::Integer(3, 32), "10", V1_12, "Chunks sent to players in each direction."),
+    spec("white-list", Kind::Boolean, "false", V1_12, "Only let whitelisted players join."),
+];
+
+#[derive(Serialize, Debug)]
+pub struct PropertySchema {
+    pub key: &'static str,
+    /// `boolean`, `integer`, `enum` or `string`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub allowed: Vec<&'static str>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub default: &'static str,
+    pub description: &'static str,
+    pub requires_restart: bool,
+    /// Only used when a new world is generated.
+    pub new_world_only: bool,
+}
+
+impl From<&Spec> for PropertySchema {
+    fn from(spec: &Spec) -> Self {
+        let (kind, allowed, range) = match spec.kind {
+            Kind::Boolean => ("boolean", vec!["true", "false"], None),
+            Kind::Integer(min, max) => ("integer", Vec::new(), Some((min, max))),
+            Kind::Enum(values) => ("enum", values.to_vec(), None),
+            Kind::String => ("string", Vec::new(), None),
+        };
+        PropertySchema {
+            key: spec.key,
+            kind,
+            allowed,
+            min: range.map(|r| r.0),
+            max: range.map(|r| r.1),
+            default: spec.default,
+            description: spec.description,
+            requires_restart: spec.applies == Applies::Restart,
+            new_world_only: spec.applies == Applies::NewWorld,
+        }
+    }
+}
+
+fn check(spec: &Spec, value: &str) -> Result<(), String> {
+    match spec.kind {
+        Kind::Boolean if !value.eq_ignore_ascii_case("true") && !value.eq_ignore_ascii_case("false") => Err("must be true or false".into()),
+        Kind::Integer(min, max) => match value.trim().parse::<i64>() {
+            Ok(n) if n < min || n > max => Err(format!("must be between {} and {}", min, max)),
+            Ok(_) => Ok(()),
+            Err(_) => Err("must be a whole number".into()),
+        },
+        Kind::Enum(values) => {
+            // Numeric ids from before 1.14 are still accepted for gamemode and difficulty
+            let legacy_id = matches!(spec.key, "gamemode" | "difficulty") && value.parse::<usize>().is_ok_and(|id| id < values.len());
+            if values.contains(&value) || legacy_id { Ok(()) } else { Err(format!("must be one of {}", values.join(", "))) }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks every known key, returning one `key: problem` line per invalid value. Keys the schema
+/// does not know (from mods or newer versions) are accepted as they are.
+pub(crate) fn validate(props: &HashMap<String, String>) -> Result<(), String> {
+    let mut errors: Vec<String> = props.iter()
+        .filter_map(|(key, value)| {
+            let spec = SCHEMA.iter().find(|s| s.key == key)?;
+            check(spec, value).err().map(|e| format!("{}: {}", key, e))
+        })
+        .collect();
+    if errors.is_empty() { return Ok(()); }
+    errors.sort();
+    Err(errors.join("\n"))
+}
+
+/// Known properties for the server's version, or every current key when the version is unknown.
+#[tauri::command]
+pub async fn get_properties_schema(path: String, state: State<'_, AppState>) -> Result<Vec<PropertySchema>, String> {
+    let jar_name = state.config.lock().unwrap().as_ref().filter(|c| c.path == path).map(|c| c.jar_name.clone());
+    let data_version = jar_name.and_then(|jar| worlds::server_data_version(&path, &jar));
+    Ok(SCHEMA.iter()
+        .filter(|s| match data_version {
+            Some(dv) => s.since <= dv && s.until.is_none_or(|until| dv < until),
+            None => s.until.is_none(),
+        })
+        .map(PropertySchema::from)
+        .collect())
+}
@@ -0,0 +1,79 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+const PACKET_RESPONSE: i32 = 0;
+const PACKET_COMMAND: i32 = 2;
+const PACKET_LOGIN: i32 = 3;
+
+/// Minimal Source RCON client, enough to drive a vanilla/Paper server's console
+/// when the process was not started by this manager.
+pub struct RconClient {
+    stream: TcpStream,
+    next_id: i32,
+}
+
+impl RconClient {
+    pub fn connect(port: u16, password: &str) -> Result<Self, String> {
+        let stream = TcpStream::connect(("127.0.0.1", port)).map_err(|e| format!("RCON connection failed: {}", e))?;
+        stream.set_read_timeout(Some(Duration::from_secs(30))).map_err(|e| e.to_string())?;
+        let mut client = RconClient { stream, next_id: 1 };
+        let id = client.send(PACKET_LOGIN, password)?;
+        // Some servers send an empty response before the auth result (which shares type 2 with commands).
+        loop {
+            let (reply_id, kind, _) = client.receive()?;
+            if kind != PACKET_COMMAND { continue; }
+            if reply_id != id { return Err("RCON authentication failed".into()); }
+            return Ok(client);
+        }
+    }
+
+    /// Connects using the `enable-rcon`, `rcon.port` and `rcon.password` keys of `server.properties`.
+    /// Returns `None` when RCON is disabled or has no password.
+    pub fn from_properties(server_path: &str) -> Option<Result<Self, String>> {
+        let props = crate::load_properties(server_path);
+        if props.get("enable-rcon").map(|v| v.as_str()) != Some("true") { return None; }
+        let password = props.get("rcon.password").filter(|p| !p.is_empty())?;
+        let port = props.get("rcon.port").and_then(|p| p.parse().ok()).unwrap_or(25575);
+        Some(Self::connect(port, password))
+    }
+
+    pub fn command(&mut self, command: &str) -> Result<String, String> {
+        let id = self.send(PACKET_COMMAND, command)?;
+        // Long responses are split across packets; an empty follow-up marks the end.
+        let end_id = self.send(PACKET_RESPONSE, "")?;
+        let mut body = String::new();
+        loop {
+            let (reply_id, kind, payload) = self.receive()?;
+            if reply_id == end_id { break; }
+            if reply_id == id && kind == PACKET_RESPONSE { body.push_str(&payload); }
+        }
+        Ok(body)
+    }
+
+    fn send(&mut self, kind: i32, body: &str) -> Result<i32, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut packet = Vec::with_capacity(body.len() + 14);
+        packet.extend_from_slice(&((body.len() + 10) as i32).to_le_bytes());
+        packet.extend_from_slice(&id.to_le_bytes());
+        packet.extend_from_slice(&kind.to_le_bytes());
+        packet.extend_from_slice(body.as_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        self.stream.write_all(&packet).map_err(|e| e.to_string())?;
+        Ok(id)
+    }
+
+    fn receive(&mut self) -> Result<(i32, i32, String), String> {
+        let mut len_buf = [0u8; 4];
+        self.stream.read_exact(&mut len_buf).map_err(|e| e.to_string())?;
+        let len = i32::from_le_bytes(len_buf);
+        if !(10..=4106).contains(&len) { return Err(format!("Invalid RCON packet length {}", len)); }
+        let mut buf = vec![0u8; len as usize];
+        self.stream.read_exact(&mut buf).map_err(|e| e.to_string())?;
+        let id = i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+        let kind = i32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+        let body = String::from_utf8_lossy(&buf[8..buf.len() - 2]).to_string();
+        Ok((id, kind, body))
+    }
+}
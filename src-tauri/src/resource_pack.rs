@@ -0,0 +1,158 @@
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+use crate::AppState;
+
+/// A running built-in pack server. It answers `GET /<sha1>.zip` with the current pack.
+pub struct PackServer {
+    port: u16,
+    route: Arc<Mutex<(String, PathBuf)>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl PackServer {
+    fn shutdown(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the blocking accept() so the thread sees the flag
+        let _ = TcpStream::connect(("127.0.0.1", self.port));
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ResourcePackResult {
+    pub url: String,
+    pub sha1: String,
+    /// Port of the built-in server, when the pack is served by the manager.
+    pub served_port: Option<u16>,
+}
+
+fn pack_file(server_path: &str) -> PathBuf {
+    Path::new(server_path).join("roam_resource_pack").join("pack.zip")
+}
+
+fn sha1_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha1::new();
+    io::copy(&mut file, &mut hasher).map_err(|e| e.to_string())?;
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// The address other machines on the network reach this one at. No packet is sent.
+fn lan_address() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    Some(socket.local_addr().ok()?.ip().to_string())
+}
+
+fn handle_request(mut stream: TcpStream, route: &Mutex<(String, PathBuf)>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() { break; }
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (expected, path) = route.lock().unwrap().clone();
+    let file = if (method == "GET" || method == "HEAD") && target == expected { fs::File::open(&path).ok() } else { None };
+    let Some(mut file) = file else {
+        return stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    };
+    let length = file.metadata()?.len();
+    write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/zip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", length)?;
+    if method == "GET" { io::copy(&mut file, &mut stream)?; }
+    stream.flush()
+}
+
+fn start_pack_server(port: u16, route: (String, PathBuf)) -> Result<PackServer, String> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| format!("Could not listen on port {}: {}", port, e))?;
+    let server = PackServer { port, route: Arc::new(Mutex::new(route)), stop: Arc::new(AtomicBool::new(false)) };
+    let (route, stop) = (server.route.clone(), server.stop.clone());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            if stop.load(Ordering::SeqCst) { break; }
+            let Ok(stream) = stream else { continue; };
+            let route = route.clone();
+            std::thread::spawn(move || { let _ = handle_request(stream, &route); });
+        }
+    });
+    Ok(server)
+}
+
+/// Serves the server's stored pack on `port`, reusing the running server when the port matches.
+/// Called when a pack is configured and again from `start_server`.
+pub(crate) fn ensure_serving(state: &AppState, server_path: &str, port: u16) -> Result<(), String> {
+    let path = pack_file(server_path);
+    if !path.exists() { return Err("No resource pack has been stored for this server".into()); }
+    let sha1 = crate::load_properties(server_path).get("resource-pack-sha1").cloned().unwrap_or_default();
+    let route = (format!("/{}.zip", sha1), path);
+    let mut current = state.pack_server.lock().unwrap();
+    if let Some(server) = current.as_ref().filter(|s| s.port == port) {
+        *server.route.lock().unwrap() = route;
+        return Ok(());
+    }
+    if let Some(server) = current.take() { server.shutdown(); }
+    *current = Some(start_pack_server(port, route)?);
+    Ok(())
+}
+
+/// Validates a resource pack zip and points `server.properties` at it with its SHA-1.
+/// With `external_url` the pack must already be uploaded there; otherwise it is copied into the
+/// server folder and served on `serve_port`, announced as `public_host` or this machine's LAN address.
+#[tauri::command]
+pub async fn set_resource_pack(server_path: String, source: String, external_url: Option<String>, serve_port: Option<u16>, public_host: Option<String>, require: bool, state: State<'_, AppState>) -> Result<ResourcePackResult, String> {
+    let source_path = Path::new(&source);
+    if !source_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) { return Err("Select a resource pack .zip file".into()); }
+    crate::datapacks::read_mcmeta(source_path)?;
+    let sha1 = sha1_file(source_path)?;
+    let external_url = external_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+
+    let (url, served_port) = match (external_url, serve_port) {
+        (Some(url), _) => {
+            if !url.starts_with("http://") && !url.starts_with("https://") { return Err("The pack URL must start with http:// or https://".into()); }
+            (url, None)
+        }
+        (None, Some(port)) => {
+            let host = public_host.map(|h| h.trim().to_string()).filter(|h| !h.is_empty()).or_else(lan_address).ok_or("Could not determine this machine's address; enter a public host")?;
+            (format!("http://{}:{}/{}.zip", host, port, sha1), Some(port))
+        }
+        (None, None) => return Err("Enter the pack's download URL or a port to serve it on".into()),
+    };
+
+    if served_port.is_some() {
+        let target = pack_file(&server_path);
+        fs::create_dir_all(target.parent().unwrap()).map_err(|e| e.to_string())?;
+        fs::copy(source_path, &target).map_err(|e| e.to_string())?;
+    }
+    let props = HashMap::from([
+        ("resource-pack".to_string(), url.clone()),
+        ("resource-pack-sha1".to_string(), sha1.clone()),
+        ("require-resource-pack".to_string(), require.to_string()),
+    ]);
+    crate::properties::patch(&server_path, &props, &[])?;
+    match served_port {
+        Some(port) => ensure_serving(&state, &server_path, port)?,
+        // The pack now comes from the external URL, so the built-in server has nothing to serve
+        None => if let Some(server) = state.pack_server.lock().unwrap().take() { server.shutdown(); },
+    }
+
+    if let Some(config) = state.config.lock().unwrap().as_mut().filter(|c| c.path == server_path) {
+        config.resource_pack_port = served_port;
+    }
+    Ok(ResourcePackResult { url, sha1, served_port })
+}
+
+#[tauri::command]
+pub async fn stop_resource_pack_server(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(server) = state.pack_server.lock().unwrap().take() { server.shutdown(); }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Serializes read-modify-write cycles between the log reader thread and commands.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Session {
+    pub player: String,
+    pub joined: String,
+    /// `None` while the player is still online.
+    pub left: Option<String>,
+    /// The machine that ran the server; `None` for sessions rebuilt from old logs.
+    pub host: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SessionView {
+    #[serde(flatten)]
+    pub session: Session,
+    pub minutes: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PeakPlayers {
+    pub peak: usize,
+    pub at: Option<String>,
+    pub players: Vec<String>,
+}
+
+pub(crate) enum Event {
+    Join(String),
+    Leave(String),
+}
+
+fn log_path(server_path: &str) -> PathBuf {
+    Path::new(server_path).join("roam_sessions.json")
+}
+
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, TIME_FORMAT).ok()
+}
+
+fn format_time(time: NaiveDateTime) -> String {
+    time.format(TIME_FORMAT).to_string()
+}
+
+fn now() -> String {
+    chrono::Local::now().format(TIME_FORMAT).to_string()
+}
+
+/// The text after the console prefix, as printed by vanilla (`[12:00:00] [Server thread/INFO]: `)
+/// and Paper (`[12:00:00 INFO]: `).
+pub(crate) fn console_message(line: &str) -> Option<&str> {
+    Some(line.split_once("]: ")?.1.trim())
+}
+
+/// Recognizes `Steve joined the game` / `Steve left the game` in a console line. Chat messages
+/// never match since the sender prefix makes the name invalid.
+pub(crate) fn parse_event(line: &str) -> Option<Event> {
+    let message = console_message(line)?;
+    let (name, joined) = match message.strip_suffix(" joined the game") {
+        Some(name) => (name, true),
+        None => (message.strip_suffix(" left the game")?, false),
+    };
+    // Renamed players are announced as "New (formerly known as Old) joined the game"
+    let name = name.split(" (formerly known as ").next().unwrap_or(name);
+    if name.is_empty() || name.len() > 16 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') { return None; }
+    Some(if joined { Event::Join(name.to_string()) } else { Event::Leave(name.to_string()) })
+}
+
+fn line_time(line: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(line.strip_prefix('[')?.get(..8)?, "%H:%M:%S").ok()
+}
+
+/// Rebuilds sessions from one archived log such as `logs/2024-05-01-2.log.gz`. Lines only carry
+/// a time, so the date comes from the file name and rolls over when the clock wraps.
+fn sessions_from_archive(path: &Path) -> Vec<Session> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let Some(mut date) = name.get(..10).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) else { return Vec::new(); };
+    let Ok(file) = fs::File::open(path) else { return Vec::new(); };
+    let mut sessions: Vec<Session> = Vec::new();
+    let mut last_time: Option<NaiveTime> = None;
+    let mut last_seen = None;
+    for line in BufReader::new(GzDecoder::new(file)).lines().map_while(Result::ok) {
+        let Some(time) = line_time(&line) else { continue; };
+        if last_time.is_some_and(|t| time < t) { date = date.succ_opt().unwrap_or(date); }
+        last_time = Some(time);
+        let at = format_time(date.and_time(time));
+        match parse_event(&line) {
+            Some(Event::Join(player)) => sessions.push(Session { player, joined: at.clone(), left: None, host: None }),
+            Some(Event::Leave(player)) => {
+                if let Some(open) = sessions.iter_mut().rev().find(|s| s.player == player && s.left.is_none()) { open.left = Some(at.clone()); }
+            }
+            None => {}
+        }
+        last_seen = Some(at);
+    }
+    // Crashes leave sessions open; end them at the last line of the log
+    for session in sessions.iter_mut().filter(|s| s.left.is_none()) { session.left = last_seen.clone(); }
+    sessions
+}
+
+fn import_archives(server_path: &str) -> Vec<Session> {
+    let Ok(entries) = fs::read_dir(Path::new(server_path).join("logs")) else { return Vec::new(); };
+    let mut archives: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path())
+        .filter(|p| p.to_string_lossy().ends_with(".log.gz")).collect();
+    archives.sort();
+    let mut sessions: Vec<Session> = archives.iter().flat_map(|p| sessions_from_archive(p)).collect();
+    sessions.sort_by(|a, b| a.joined.cmp(&b.joined));
+    sessions
+}
+
+/// Reads the session log. `None` when it does not exist yet.
+fn read(server_path: &str) -> Result<Option<Vec<Session>>, String> {
+    let content = match fs::read_to_string(log_path(server_path)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&content).map(Some).map_err(|e| format!("roam_sessions.json is damaged: {}", e))
+}
+
+/// Loads the session log for an update, rebuilding it from `logs/*.log.gz` the first time.
+/// A damaged file is moved aside as `roam_sessions.json.bad-<timestamp>` and rebuilt.
+fn load(server_path: &str) -> Result<Vec<Session>, String> {
+    match read(server_path) {
+        Ok(Some(sessions)) => Ok(sessions),
+        Ok(None) => Ok(import_archives(server_path)),
+        Err(e) if log_path(server_path).exists() => {
+            let aside = log_path(server_path).with_extension(format!("json.bad-{}", chrono::Local::now().format("%Y%m%d%H%M%S")));
+            fs::rename(log_path(server_path), &aside).map_err(|_| e)?;
+            Ok(import_archives(server_path))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes through a temp file so a crash mid-write cannot truncate the log.
+fn save(server_path: &str, sessions: &[Session]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(sessions).map_err(|e| e.to_string())?;
+    let tmp = log_path(server_path).with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, log_path(server_path)).map_err(|e| e.to_string())
+}
+
+fn update(server_path: &str, change: impl FnOnce(&mut Vec<Session>)) {
+    let _guard = FILE_LOCK.lock().unwrap();
+    let Ok(mut sessions) = load(server_path) else { return; };
+    change(&mut sessions);
+    let _ = save(server_path, &sessions);
+}
+
+/// The recorded sessions for the query commands, which never import or write anything.
+fn recorded(server_path: &str) -> Result<Vec<Session>, String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    Ok(read(server_path)?.unwrap_or_default())
+}
+
+/// Creates the session log from old logs if it does not exist yet. Called when the server starts.
+pub(crate) fn import_if_missing(server_path: &str) {
+    if !log_path(server_path).exists() { update(server_path, |_| {}); }
+}
+
+/// Records a join or leave seen in the live console.
+pub(crate) fn record(server_path: &str, event: &Event) {
+    match event {
+        Event::Join(player) => update(server_path, |sessions| {
+            let host = sysinfo::System::host_name();
+            sessions.push(Session { player: player.clone(), joined: now(), left: None, host });
+        }),
+        Event::Leave(player) => update(server_path, |sessions| {
+            if let Some(open) = sessions.iter_mut().rev().find(|s| &s.player == player && s.left.is_none()) { open.left = Some(now()); }
+        }),
+    }
+}
+
+/// Ends every open session, for when the server stops or crashes without "left the game" lines.
+pub(crate) fn close_open(server_path: &str) {
+    update(server_path, |sessions| {
+        let at = now();
+        for session in sessions.iter_mut().filter(|s| s.left.is_none()) { session.left = Some(at.clone()); }
+    });
+}
+
+fn bounds(session: &Session) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let joined = parse_time(&session.joined)?;
+    let left = session.left.as_deref().and_then(parse_time).unwrap_or_else(|| chrono::Local::now().naive_local());
+    Some((joined, left.max(joined)))
+}
+
+fn in_range(session: &Session, since: Option<&str>) -> bool {
+    since.is_none_or(|since| session.left.as_deref().is_none_or(|left| left >= since))
+}
+
+/// Sessions newest first, optionally for one player (case-insensitive) and since a date.
+#[tauri::command]
+pub async fn get_player_sessions(server_path: String, player: Option<String>, since: Option<String>) -> Result<Vec<SessionView>, String> {
+    let sessions = recorded(&server_path)?;
+    Ok(sessions.into_iter().rev()
+        .filter(|s| player.as_ref().is_none_or(|p| s.player.eq_ignore_ascii_case(p)))
+        .filter(|s| in_range(s, since.as_deref()))
+        .map(|session| {
+            let minutes = bounds(&session).map_or(0, |(joined, left)| (left - joined).num_minutes());
+            SessionView { session, minutes }
+        })
+        .collect())
+}
+
+/// The most players online at once, and when that first happened.
+#[tauri::command]
+pub async fn get_peak_players(server_path: String, since: Option<String>) -> Result<PeakPlayers, String> {
+    let sessions = recorded(&server_path)?;
+    let mut events: Vec<(NaiveDateTime, bool, &str)> = Vec::new();
+    for session in sessions.iter().filter(|s| in_range(s, since.as_deref())) {
+        let Some((joined, left)) = bounds(session) else { continue; };
+        events.push((joined, true, &session.player));
+        events.push((left, false, &session.player));
+    }
+    // Leaves sort before joins at the same second so a quick relog is not counted twice
+    events.sort_by_key(|(time, join, _)| (*time, *join));
+    let mut online: Vec<&str> = Vec::new();
+    let mut peak = PeakPlayers { peak: 0, at: None, players: Vec::new() };
+    for (time, join, player) in events {
+        if join { online.push(player); } else if let Some(i) = online.iter().position(|p| *p == player) { online.remove(i); }
+        if online.len() > peak.peak {
+            peak = PeakPlayers { peak: online.len(), at: Some(format_time(time)), players: online.iter().map(|p| p.to_string()).collect() };
+        }
+    }
+    Ok(peak)
+}
+
+/// Minutes played in each hour of the day (index 0 is midnight to 1am), summed over all players.
+#[tauri::command]
+pub async fn get_activity_by_hour(server_path: String, player: Option<String>, since: Option<String>) -> Result<Vec<i64>, String> {
+    let sessions = recorded(&server_path)?;
+    let mut seconds = vec![0i64; 24];
+    for session in sessions.iter()
+        .filter(|s| player.as_ref().is_none_or(|p| s.player.eq_ignore_ascii_case(p)))
+        .filter(|s| in_range(s, since.as_deref())) {
+        let Some((mut cursor, left)) = bounds(session) else { continue; };
+        while cursor < left {
+            let hour_end = cursor.with_minute(0).and_then(|t| t.with_second(0)).unwrap_or(cursor) + Duration::hours(1);
+            let slice_end = hour_end.min(left);
+            seconds[cursor.hour() as usize] += (slice_end - cursor).num_seconds();
+            cursor = slice_end;
+        }
+    }
+    Ok(seconds.into_iter().map(|s| s / 60).collect())
+}
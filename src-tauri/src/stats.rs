@@ -0,0 +1,127 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::{profiles, worlds, AppState};
+
+const CATEGORIES: [&str; 9] = ["mined", "crafted", "used", "broken", "picked_up", "dropped", "killed", "killed_by", "custom"];
+
+#[derive(Serialize, Debug, Clone)]
+pub struct StatValue {
+    pub key: String,
+    pub raw: i64,
+    /// `raw` converted to `unit`: metres for distances, hours for time, hearts for damage.
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlayerStats {
+    pub uuid: String,
+    pub name: String,
+    pub data_version: Option<i64>,
+    /// Keyed by category without the `minecraft:` prefix, e.g. `mined` or `custom`.
+    pub categories: BTreeMap<String, Vec<StatValue>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub uuid: String,
+    pub name: String,
+    pub raw: i64,
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+fn namespaced(id: &str) -> String {
+    if id.contains(':') { id.to_string() } else { format!("minecraft:{}", id) }
+}
+
+/// Converts a raw stat to a readable unit. Distances are stored in centimetres, times in ticks and
+/// damage in tenths of a health point.
+fn convert(category: &str, key: &str, raw: i64) -> (f64, &'static str) {
+    if category != "minecraft:custom" { return (raw as f64, "count"); }
+    let stat = key.strip_prefix("minecraft:").unwrap_or(key);
+    if stat.ends_with("_one_cm") { return (raw as f64 / 100.0, "m"); }
+    if stat.starts_with("damage_") { return (raw as f64 / 20.0, "hearts"); }
+    if matches!(stat, "play_time" | "play_one_minute" | "total_world_time" | "time_since_death" | "time_since_rest" | "sneak_time") {
+        return (raw as f64 / 20.0 / 3600.0, "h");
+    }
+    (raw as f64, "count")
+}
+
+fn stats_dir(server_path: &str) -> PathBuf {
+    Path::new(server_path).join(worlds::active_world_name(server_path)).join("stats")
+}
+
+fn read_stats(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn stat_value(category: &str, key: &str, raw: i64) -> StatValue {
+    let (value, unit) = convert(category, key, raw);
+    StatValue { key: key.to_string(), raw, value, unit }
+}
+
+/// Every statistic recorded for a player in the active world, sorted by value within each category.
+#[tauri::command]
+pub async fn get_player_stats(server_path: String, uuid: String, state: State<'_, AppState>) -> Result<PlayerStats, String> {
+    crate::validate_folder_name(&uuid)?;
+    let path = stats_dir(&server_path).join(format!("{}.json", uuid));
+    if !path.exists() { return Err(format!("No statistics recorded for {}", uuid)); }
+    let json = read_stats(&path)?;
+
+    let mut categories = BTreeMap::new();
+    if let Some(stats) = json.get("stats").and_then(Value::as_object) {
+        for (category, values) in stats {
+            let Some(values) = values.as_object() else { continue; };
+            let mut list: Vec<StatValue> = values.iter().filter_map(|(key, raw)| Some(stat_value(category, key, raw.as_i64()?))).collect();
+            list.sort_by_key(|s| std::cmp::Reverse(s.raw));
+            categories.insert(category.strip_prefix("minecraft:").unwrap_or(category).to_string(), list);
+        }
+    } else if let Some(legacy) = json.as_object() {
+        // Before 1.13 stats were a flat object of `stat.*` keys
+        let list = legacy.iter().filter_map(|(key, raw)| Some(stat_value("legacy", key, raw.as_i64()?))).collect();
+        categories.insert("legacy".to_string(), list);
+    }
+    let names = profiles::names_for_uuids(&state, &server_path, std::slice::from_ref(&uuid)).await;
+    Ok(PlayerStats {
+        name: names.get(&uuid).cloned().unwrap_or_else(|| uuid.clone()),
+        uuid,
+        data_version: json.get("DataVersion").and_then(Value::as_i64),
+        categories,
+    })
+}
+
+/// Ranks every player in the active world by one statistic, e.g. `custom` + `walk_one_cm`.
+/// Players who never recorded the stat are left out.
+#[tauri::command]
+pub async fn get_stat_leaderboard(server_path: String, category: String, key: String, limit: Option<usize>, state: State<'_, AppState>) -> Result<Vec<LeaderboardEntry>, String> {
+    let (category, key) = (namespaced(&category), namespaced(&key));
+    if !CATEGORIES.iter().any(|c| category == namespaced(c)) { return Err(format!("Unknown stat category '{}'", category)); }
+    let Ok(entries) = fs::read_dir(stats_dir(&server_path)) else { return Ok(Vec::new()); };
+
+    let mut scores = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") { continue; }
+        let Ok(json) = read_stats(&path) else { continue; };
+        if let Some(raw) = json["stats"][&category][&key].as_i64() {
+            scores.push((path.file_stem().unwrap().to_string_lossy().to_string(), raw));
+        }
+    }
+    scores.sort_by_key(|(_, raw)| std::cmp::Reverse(*raw));
+    scores.truncate(limit.unwrap_or(10));
+
+    let uuids: Vec<String> = scores.iter().map(|(uuid, _)| uuid.clone()).collect();
+    let names = profiles::names_for_uuids(&state, &server_path, &uuids).await;
+    Ok(scores.into_iter().enumerate().map(|(i, (uuid, raw))| {
+        let (value, unit) = convert(&category, &key, raw);
+        LeaderboardEntry { rank: i + 1, name: names.get(&uuid).cloned().unwrap_or_else(|| uuid.clone()), uuid, raw, value, unit }
+    }).collect())
+}
@@ -0,0 +1,238 @@
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+use walkdir::WalkDir;
+
+use crate::backups::{self, ZipSource};
+use crate::nbt;
+use crate::{AppState, ServerFlavor};
+
+/// Progress for long imports/exports, emitted as `world-transfer-progress`.
+#[derive(Serialize, Clone, Debug)]
+pub struct TransferProgress {
+    pub world: String,
+    pub stage: String,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+}
+
+struct Progress<'a> {
+    app: &'a AppHandle,
+    world: &'a str,
+    stage: &'static str,
+    done: u64,
+    total: u64,
+    last_percent: u64,
+}
+
+impl<'a> Progress<'a> {
+    fn new(app: &'a AppHandle, world: &'a str, stage: &'static str, total: u64) -> Self {
+        let progress = Progress { app, world, stage, done: 0, total, last_percent: 0 };
+        progress.emit();
+        progress
+    }
+
+    fn advance(&mut self, bytes: u64) {
+        self.done += bytes;
+        let percent = (self.done * 100).checked_div(self.total).unwrap_or(100);
+        if percent > self.last_percent {
+            self.last_percent = percent;
+            self.emit();
+        }
+    }
+
+    fn emit(&self) {
+        let _ = self.app.emit("world-transfer-progress", TransferProgress {
+            world: self.world.to_string(),
+            stage: self.stage.to_string(),
+            done_bytes: self.done,
+            total_bytes: self.total,
+        });
+    }
+}
+
+/// Finds the shallowest folder under `dir` that contains a `level.dat`.
+fn find_world_root(dir: &Path) -> Option<PathBuf> {
+    WalkDir::new(dir).max_depth(4).into_iter().filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "level.dat")
+        .min_by_key(|e| e.depth())
+        .and_then(|e| e.path().parent().map(Path::to_path_buf))
+}
+
+fn copy_folder(source: &Path, dest: &Path, progress: &mut Progress) -> Result<(), String> {
+    let total: u64 = WalkDir::new(source).into_iter().filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).filter(|m| m.is_file()).map(|m| m.len()).sum();
+    progress.total = total;
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let rel = entry.path().strip_prefix(source).map_err(|e| e.to_string())?;
+        let out_path = dest.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+        } else if entry.file_type().is_file() && entry.file_name() != "session.lock" {
+            progress.advance(fs::copy(entry.path(), &out_path).map_err(|e| e.to_string())?);
+        }
+    }
+    Ok(())
+}
+
+/// Extracts only the entries under the archive's world root (the folder holding `level.dat`).
+fn extract_world_zip(archive_path: &Path, dest: &Path, progress: &mut Progress) -> Result<(), String> {
+    let file = fs::File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut root: Option<PathBuf> = None;
+    let mut total = 0;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = entry.enclosed_name().ok_or_else(|| format!("Archive entry '{}' escapes the world folder", entry.name()))?;
+        total += entry.size();
+        if name.file_name().is_some_and(|n| n == "level.dat") {
+            let parent = name.parent().map(Path::to_path_buf).unwrap_or_default();
+            if root.as_ref().is_none_or(|r| parent.components().count() < r.components().count()) { root = Some(parent); }
+        }
+    }
+    let root = root.ok_or("Archive does not contain a world (no level.dat found)")?;
+    progress.total = total;
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = entry.enclosed_name().unwrap();
+        let Ok(rel) = name.strip_prefix(&root) else { continue; };
+        let out_path = dest.join(rel);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if rel.as_os_str() == "session.lock" { continue; }
+        if let Some(parent) = out_path.parent() { fs::create_dir_all(parent).map_err(|e| e.to_string())?; }
+        let mut out = fs::File::create(&out_path).map_err(|e| e.to_string())?;
+        progress.advance(io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?);
+    }
+    Ok(())
+}
+
+/// Drops the singleplayer host's inventory/position (`Data.Player`) so they do not spawn with it.
+fn remove_owner_player(world_dir: &Path) -> Result<(), String> {
+    let level_path = world_dir.join("level.dat");
+    let (root_name, mut root) = nbt::read_file(&level_path)?;
+    let data = root.compound_mut("Data").ok_or("level.dat has no Data compound")?;
+    if data.remove("Player").is_some() { nbt::write_file(&level_path, &root_name, &root)?; }
+    Ok(())
+}
+
+/// Moves singleplayer `DIM-1`/`DIM1` folders into Bukkit's `<world>_nether`/`<world>_the_end`,
+/// adding each folder to `created` as soon as it exists so a failure can be cleaned up.
+fn split_dimensions(server_path: &str, world_name: &str, created: &mut Vec<String>) -> Result<(), String> {
+    let world_dir = Path::new(server_path).join(world_name);
+    for (dim, suffix) in [("DIM-1", "_nether"), ("DIM1", "_the_end")] {
+        let source = world_dir.join(dim);
+        if !source.is_dir() { continue; }
+        let folder = format!("{}{}", world_name, suffix);
+        let target = Path::new(server_path).join(&folder);
+        fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+        created.push(folder);
+        fs::rename(&source, target.join(dim)).map_err(|e| e.to_string())?;
+        fs::copy(world_dir.join("level.dat"), target.join("level.dat")).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Copies a world from a zip file or a folder (such as `.minecraft/saves/<world>`) into the
+/// server under `new_name`. Returns the folders created.
+#[tauri::command]
+pub async fn import_world(app: AppHandle, server_path: String, source: String, new_name: String, remove_owner: bool, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    crate::validate_folder_name(&new_name)?;
+    let target = Path::new(&server_path).join(&new_name);
+    if target.exists() { return Err(format!("A folder named '{}' already exists", new_name)); }
+    let jar_name = state.config.lock().unwrap().as_ref().filter(|c| c.path == server_path).map(|c| c.jar_name.clone());
+    let split = crate::detect_server_flavor(&server_path, jar_name.as_deref()) != ServerFlavor::Vanilla;
+    if split {
+        for suffix in ["_nether", "_the_end"] {
+            let folder = format!("{}{}", new_name, suffix);
+            if Path::new(&server_path).join(&folder).exists() { return Err(format!("A folder named '{}' already exists", folder)); }
+        }
+    }
+
+    let source_path = Path::new(&source);
+    let staging = Path::new(&server_path).join(format!(".roam_import_{}", chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")));
+    let mut progress = Progress::new(&app, &new_name, "copy", 0);
+    let copied = if source_path.is_dir() {
+        find_world_root(source_path).ok_or_else(|| "No level.dat found in the selected folder".to_string())
+            .and_then(|root| copy_folder(&root, &staging, &mut progress))
+    } else if source_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) {
+        extract_world_zip(source_path, &staging, &mut progress)
+    } else {
+        Err("Select a world folder or a .zip archive".into())
+    };
+    let prepared = copied.and_then(|_| if remove_owner { remove_owner_player(&staging) } else { Ok(()) });
+    if let Err(e) = prepared.and_then(|_| fs::rename(&staging, &target).map_err(|e| e.to_string())) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    let mut folders = vec![new_name.clone()];
+    if split {
+        if let Err(e) = split_dimensions(&server_path, &new_name, &mut folders) {
+            // Everything here is a fresh copy, so drop the partial import rather than leave it half split
+            for folder in &folders { let _ = fs::remove_dir_all(Path::new(&server_path).join(folder)); }
+            return Err(e);
+        }
+    }
+    progress.stage = "done";
+    progress.emit();
+    Ok(folders)
+}
+
+fn folder_size(dir: &Path, skip: &[&str]) -> u64 {
+    WalkDir::new(dir).into_iter()
+        .filter_entry(|e| !(e.depth() == 1 && skip.iter().any(|s| e.file_name() == *s)))
+        .filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).filter(|m| m.is_file()).map(|m| m.len()).sum()
+}
+
+/// Zips a world in the singleplayer layout (one folder with `DIM-1`/`DIM1` inside), merging
+/// Bukkit's split dimension folders back in. Returns the path of the archive.
+#[tauri::command]
+pub async fn export_world(app: AppHandle, server_path: String, world_name: String, strip_player_data: bool, destination: Option<String>) -> Result<String, String> {
+    crate::validate_folder_name(&world_name)?;
+    // Waiting for the save and zipping block, so keep them off the async runtime
+    tauri::async_runtime::spawn_blocking(move || write_export(&app, &server_path, &world_name, strip_player_data, destination))
+        .await.map_err(|e| e.to_string())?
+}
+
+fn write_export(app: &AppHandle, server_path: &str, world_name: &str, strip_player_data: bool, destination: Option<String>) -> Result<String, String> {
+    let root = Path::new(server_path);
+    let world_dir = root.join(world_name);
+    if !world_dir.join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+
+    let nether = root.join(format!("{}_nether", world_name)).join("DIM-1");
+    let end = root.join(format!("{}_the_end", world_name)).join("DIM1");
+    let mut skip = Vec::new();
+    if strip_player_data { skip.extend(["playerdata", "stats", "advancements"]); }
+    // Bukkit leaves empty DIM folders in the overworld; the split folders hold the real data
+    if nether.is_dir() { skip.push("DIM-1"); }
+    if end.is_dir() { skip.push("DIM1"); }
+    let mut sources = vec![ZipSource { dir: world_dir.clone(), prefix: format!("{}/", world_name), skip: &skip }];
+    if nether.is_dir() { sources.push(ZipSource { dir: nether, prefix: format!("{}/DIM-1/", world_name), skip: &[] }); }
+    if end.is_dir() { sources.push(ZipSource { dir: end, prefix: format!("{}/DIM1/", world_name), skip: &[] }); }
+
+    let out_dir = match destination {
+        Some(dir) => PathBuf::from(dir),
+        None => backups::backup_dir(server_path).join("exports"),
+    };
+    fs::create_dir_all(&out_dir).map_err(|e| e.to_string())?;
+    let zip_path = out_dir.join(format!("{}_{}.zip", world_name, chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")));
+
+    let total = sources.iter().map(|s| folder_size(&s.dir, s.skip)).sum();
+    let mut progress = Progress::new(app, world_name, "export", total);
+    let result = backups::with_saving_paused(&app.state::<AppState>(), server_path, || {
+        backups::write_zip(&sources, &zip_path, &mut |bytes| progress.advance(bytes))
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_file(&zip_path);
+        return Err(e);
+    }
+    progress.stage = "done";
+    progress.emit();
+    Ok(zip_path.to_string_lossy().to_string())
+}
@@ -0,0 +1,419 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tauri::State;
+use walkdir::WalkDir;
+
+use crate::nbt::{self, Compound, Tag};
+use crate::AppState;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LevelInfo {
+    pub level_name: Option<String>,
+    /// Serialized as a string since 64-bit seeds do not fit in a JS number.
+    pub seed: Option<String>,
+    pub version_name: Option<String>,
+    pub data_version: Option<i64>,
+    pub game_mode: Option<String>,
+    pub difficulty: Option<String>,
+    pub hardcore: bool,
+    pub spawn: Option<[i64; 3]>,
+    pub day_time: Option<i64>,
+    pub last_played: Option<String>,
+    pub enabled_datapacks: Vec<String>,
+    pub disabled_datapacks: Vec<String>,
+    pub game_rules: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WorldBorderUpdate {
+    pub center_x: f64,
+    pub center_z: f64,
+    pub size: f64,
+}
+
+/// Fields left as `None` are not touched.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct WorldSettingsUpdate {
+    pub game_rules: Option<BTreeMap<String, String>>,
+    pub difficulty: Option<String>,
+    pub spawn: Option<[i64; 3]>,
+    pub world_border: Option<WorldBorderUpdate>,
+    pub hardcore: Option<bool>,
+    pub level_name: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WorldInfo {
+    pub name: String,
+    pub size_mb: f64,
+    pub last_modified: String,
+    /// True for the world named by `level-name` (and its split dimension folders).
+    pub active: bool,
+    /// For Bukkit's `<world>_nether` / `<world>_the_end` folders, the overworld they belong to.
+    pub dimension_of: Option<String>,
+    pub level: Option<LevelInfo>,
+    /// Set when `level.dat` was written by a newer game version than the server jar.
+    pub newer_than_server: bool,
+    pub level_error: Option<String>,
+}
+
+pub(crate) fn game_mode_name(id: i64) -> &'static str {
+    match id { 1 => "creative", 2 => "adventure", 3 => "spectator", _ => "survival" }
+}
+
+pub(crate) fn difficulty_name(id: i64) -> &'static str {
+    match id { 0 => "peaceful", 1 => "easy", 3 => "hard", _ => "normal" }
+}
+
+fn difficulty_id(name: &str) -> Result<i8, String> {
+    match name {
+        "peaceful" => Ok(0),
+        "easy" => Ok(1),
+        "normal" => Ok(2),
+        "hard" => Ok(3),
+        other => Err(format!("Unknown difficulty '{}'", other)),
+    }
+}
+
+const DIMENSION_SUFFIXES: [&str; 2] = ["_nether", "_the_end"];
+
+/// Returns the overworld name if `name` is one of Bukkit's split dimension folders for it.
+pub(crate) fn dimension_parent(server_path: &str, name: &str) -> Option<String> {
+    DIMENSION_SUFFIXES.iter()
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .find(|base| !base.is_empty() && Path::new(server_path).join(base).join("level.dat").exists())
+        .map(String::from)
+}
+
+/// The overworld folder plus any Bukkit dimension folders that exist next to it.
+pub(crate) fn world_folders(server_path: &str, world_name: &str) -> Vec<String> {
+    let mut folders = vec![world_name.to_string()];
+    for suffix in DIMENSION_SUFFIXES {
+        let folder = format!("{}{}", world_name, suffix);
+        if Path::new(server_path).join(&folder).is_dir() { folders.push(folder); }
+    }
+    folders
+}
+
+/// The world folder the server loads, from `level-name` in `server.properties`.
+pub(crate) fn active_world_name(server_path: &str) -> String {
+    crate::load_properties(server_path).get("level-name").filter(|n| !n.is_empty()).cloned().unwrap_or_else(|| "world".into())
+}
+
+fn string_list(data: &Compound, path: &str) -> Vec<String> {
+    data.path(path).and_then(|t| t.as_list())
+        .map(|items| items.iter().filter_map(|t| t.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+/// Reads `<world>/level.dat` and returns its `Data` compound.
+pub(crate) fn read_level_data(world_dir: &Path) -> Result<Compound, String> {
+    let (_, root) = nbt::read_file(&world_dir.join("level.dat"))?;
+    match root.compound("Data") {
+        Some(data) => Ok(data.clone()),
+        None => Err("level.dat has no Data compound".into()),
+    }
+}
+
+pub(crate) fn level_info(data: &Compound) -> LevelInfo {
+    // Seeds moved into WorldGenSettings in 1.16 and spawn into a `spawn` compound in 1.21.9
+    let seed = data.path("WorldGenSettings.seed").or_else(|| data.get("RandomSeed")).and_then(|t| t.as_i64()).map(|s| s.to_string());
+    let spawn = match data.path("spawn.pos").and_then(|t| t.as_int_vec()) {
+        Some(pos) if pos.len() == 3 => Some([pos[0], pos[1], pos[2]]),
+        _ => match (data.get("SpawnX"), data.get("SpawnY"), data.get("SpawnZ")) {
+            (Some(x), Some(y), Some(z)) => Some([x.as_i64().unwrap_or(0), y.as_i64().unwrap_or(0), z.as_i64().unwrap_or(0)]),
+            _ => None,
+        },
+    };
+    let last_played = data.get("LastPlayed").and_then(|t| t.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|dt| dt.with_timezone(&chrono::Local).format("%d %b %Y, %H:%M").to_string());
+    let game_rules = data.compound("GameRules")
+        .or_else(|| data.compound("game_rules"))
+        .map(|rules| rules.0.iter().map(|(k, v)| (k.clone(), v.to_plain_string())).collect())
+        .unwrap_or_default();
+    LevelInfo {
+        level_name: data.get("LevelName").and_then(|t| t.as_str()).map(String::from),
+        seed,
+        version_name: data.path("Version.Name").and_then(|t| t.as_str()).map(String::from),
+        data_version: data.get("DataVersion").or_else(|| data.path("Version.Id")).and_then(|t| t.as_i64()),
+        game_mode: data.get("GameType").and_then(|t| t.as_i64()).map(|id| game_mode_name(id).to_string()),
+        difficulty: data.get("Difficulty").and_then(|t| t.as_i64()).map(|id| difficulty_name(id).to_string()),
+        hardcore: data.get("hardcore").and_then(|t| t.as_i64()).unwrap_or(0) != 0,
+        spawn,
+        day_time: data.get("DayTime").and_then(|t| t.as_i64()),
+        last_played,
+        enabled_datapacks: string_list(data, "DataPacks.Enabled"),
+        disabled_datapacks: string_list(data, "DataPacks.Disabled"),
+        game_rules,
+    }
+}
+
+/// Reads the world data version from the server jar's `version.json`. Paper keeps the real
+/// server jar under `versions/`, so those are checked when the launcher jar has none.
+pub(crate) fn server_data_version(server_path: &str, jar_name: &str) -> Option<i64> {
+    let read_jar = |path: &Path| -> Option<i64> {
+        let mut archive = zip::ZipArchive::new(fs::File::open(path).ok()?).ok()?;
+        let mut content = String::new();
+        archive.by_name("version.json").ok()?.read_to_string(&mut content).ok()?;
+        let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+        json["world_version"].as_i64()
+    };
+    let root = Path::new(server_path);
+    read_jar(&root.join(jar_name)).or_else(|| {
+        WalkDir::new(root.join("versions")).max_depth(2).into_iter().filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "jar"))
+            .find_map(|e| read_jar(e.path()))
+    })
+}
+
+#[tauri::command]
+pub async fn get_worlds(path: String, state: State<'_, AppState>) -> Result<Vec<WorldInfo>, String> {
+    let jar_name = state.config.lock().unwrap().as_ref().filter(|c| c.path == path).map(|c| c.jar_name.clone());
+    let server_version = jar_name.and_then(|jar| server_data_version(&path, &jar));
+    let active_world = active_world_name(&path);
+    let server_path = path.clone();
+    let mut worlds = Vec::new();
+    let entries = fs::read_dir(&path).map_err(|e| e.to_string())?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if path.is_dir() && path.join("level.dat").exists() {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let metadata = fs::metadata(&path).map_err(|e| e.to_string())?;
+            let last_modified = if let Ok(time) = metadata.modified() {
+                let dt: chrono::DateTime<chrono::Local> = time.into();
+                dt.format("%d %b %Y, %H:%M").to_string()
+            } else { "Unknown".to_string() };
+            let total_size: u64 = WalkDir::new(&path).into_iter().filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).filter(|m| m.is_file()).map(|m| m.len()).sum();
+            let (level, level_error) = match read_level_data(&path) {
+                Ok(data) => (Some(level_info(&data)), None),
+                Err(e) => (None, Some(e)),
+            };
+            let newer_than_server = match (level.as_ref().and_then(|l| l.data_version), server_version) {
+                (Some(world), Some(server)) => world > server,
+                _ => false,
+            };
+            let dimension_of = dimension_parent(&server_path, &name);
+            let active = name == active_world || dimension_of.as_deref() == Some(active_world.as_str());
+            worlds.push(WorldInfo { name, size_mb: (total_size as f64) / 1024.0 / 1024.0, last_modified, active, dimension_of, level, newer_than_server, level_error });
+        }
+    }
+    Ok(worlds)
+}
+
+/// Game rule values keep whichever tag type the world already uses for them.
+fn game_rule_tag(existing: Option<&Tag>, value: &str) -> Result<Tag, String> {
+    let invalid = || format!("Invalid game rule value '{}'", value);
+    Ok(match existing {
+        Some(Tag::Byte(_)) => Tag::Byte(value.parse::<bool>().map_err(|_| invalid())? as i8),
+        Some(Tag::Int(_)) => Tag::Int(value.parse().map_err(|_| invalid())?),
+        _ => Tag::String(value.to_string()),
+    })
+}
+
+fn apply_offline(data: &mut Compound, update: &WorldSettingsUpdate) -> Result<(), String> {
+    if let Some(rules) = &update.game_rules {
+        let key = if data.get("GameRules").is_none() && data.get("game_rules").is_some() { "game_rules" } else { "GameRules" };
+        if data.compound(key).is_none() { data.insert(key, Tag::Compound(Compound::default())); }
+        let compound = data.compound_mut(key).unwrap();
+        for (rule, value) in rules {
+            let tag = game_rule_tag(compound.get(rule), value)?;
+            compound.insert(rule, tag);
+        }
+    }
+    if let Some(difficulty) = &update.difficulty {
+        data.insert("Difficulty", Tag::Byte(difficulty_id(difficulty)?));
+    }
+    if let Some([x, y, z]) = update.spawn {
+        match data.compound_mut("spawn") {
+            Some(spawn) => spawn.insert("pos", Tag::IntArray(vec![x as i32, y as i32, z as i32])),
+            None => {
+                data.insert("SpawnX", Tag::Int(x as i32));
+                data.insert("SpawnY", Tag::Int(y as i32));
+                data.insert("SpawnZ", Tag::Int(z as i32));
+            }
+        }
+    }
+    if let Some(border) = &update.world_border {
+        data.insert("BorderCenterX", Tag::Double(border.center_x));
+        data.insert("BorderCenterZ", Tag::Double(border.center_z));
+        data.insert("BorderSize", Tag::Double(border.size));
+    }
+    if let Some(hardcore) = update.hardcore { data.insert("hardcore", Tag::Byte(hardcore as i8)); }
+    if let Some(name) = &update.level_name { data.insert("LevelName", Tag::String(name.clone())); }
+    Ok(())
+}
+
+/// Console equivalents for a running server. Hardcore and the level name can only change offline.
+fn live_commands(update: &WorldSettingsUpdate) -> Result<Vec<String>, String> {
+    if update.hardcore.is_some() || update.level_name.is_some() {
+        return Err("Hardcore mode and the level name can only be changed while the server is stopped.".into());
+    }
+    let mut commands = Vec::new();
+    for (rule, value) in update.game_rules.iter().flatten() {
+        if rule.is_empty() || !rule.chars().all(|c| c.is_ascii_alphabetic() || c == '_') {
+            return Err(format!("Invalid game rule name '{}'", rule));
+        }
+        if value != "true" && value != "false" && value.parse::<i64>().is_err() {
+            return Err(format!("Game rule {} must be true, false or a whole number", rule));
+        }
+        commands.push(format!("gamerule {} {}", rule, value));
+    }
+    if let Some(difficulty) = &update.difficulty {
+        difficulty_id(difficulty)?;
+        commands.push(format!("difficulty {}", difficulty));
+    }
+    if let Some([x, y, z]) = update.spawn { commands.push(format!("setworldspawn {} {} {}", x, y, z)); }
+    if let Some(border) = &update.world_border {
+        commands.push(format!("worldborder center {} {}", border.center_x, border.center_z));
+        commands.push(format!("worldborder set {}", border.size));
+    }
+    Ok(commands)
+}
+
+/// Edits `level.dat` directly when the world is not loaded, otherwise applies the same changes
+/// through the console. Returns a description of what was done.
+#[tauri::command]
+pub async fn update_world_settings(server_path: String, world_name: String, update: WorldSettingsUpdate, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    crate::validate_folder_name(&world_name)?;
+    let world_dir = Path::new(&server_path).join(&world_name);
+    if !world_dir.join("level.dat").exists() { return Err(format!("World '{}' not found", world_name)); }
+
+    let active = active_world_name(&server_path);
+    if crate::is_server_running(&state, &server_path) && world_folders(&server_path, &active).contains(&world_name) {
+        if world_name != active {
+            return Err(format!("'{}' is loaded by the running server. Stop it to edit this dimension.", world_name));
+        }
+        return crate::run_live(&state, &server_path, live_commands(&update)?)?
+            .ok_or_else(|| "The server is running outside the manager and RCON is not enabled. Stop it or enable RCON to change settings.".to_string());
+    }
+
+    let level_path = world_dir.join("level.dat");
+    let (root_name, mut root) = nbt::read_file(&level_path)?;
+    let data = root.compound_mut("Data").ok_or("level.dat has no Data compound")?;
+    apply_offline(data, &update)?;
+    nbt::write_file(&level_path, &root_name, &root)?;
+    Ok(vec![format!("Updated {}/level.dat (previous copy kept as level.dat_old)", world_name)])
+}
+
+/// Points `level-name` at another world folder. Only allowed while the server is stopped.
+/// Returns every folder the server will now load, including Bukkit's dimension folders.
+#[tauri::command]
+pub async fn set_active_world(server_path: String, world_name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    crate::ensure_server_stopped(&state, &server_path)?;
+    check_world(&server_path, &world_name)?;
+    crate::set_property(&server_path, "level-name", &world_name)?;
+    Ok(world_folders(&server_path, &world_name))
+}
+
+/// Refuses to touch a world the running server has loaded. Other worlds stay editable.
+fn ensure_world_unloaded(state: &AppState, server_path: &str, world_name: &str) -> Result<(), String> {
+    if active_world_name(server_path) == world_name { crate::ensure_server_stopped(state, server_path)?; }
+    Ok(())
+}
+
+/// Checks that `world_name` is an existing overworld folder rather than one of its dimensions.
+fn check_world(server_path: &str, world_name: &str) -> Result<(), String> {
+    crate::validate_folder_name(world_name)?;
+    if let Some(parent) = dimension_parent(server_path, world_name) {
+        return Err(format!("'{}' holds a dimension of '{}'. Select '{}' instead.", world_name, parent, parent));
+    }
+    if !Path::new(server_path).join(world_name).join("level.dat").exists() {
+        return Err(format!("World '{}' not found", world_name));
+    }
+    Ok(())
+}
+
+/// Like `check_world`, and also checks that `new_name` is free for the world and its dimensions.
+fn check_world_pair(server_path: &str, world_name: &str, new_name: &str) -> Result<(), String> {
+    check_world(server_path, world_name)?;
+    crate::validate_folder_name(new_name)?;
+    for suffix in std::iter::once("").chain(DIMENSION_SUFFIXES) {
+        let folder = format!("{}{}", new_name, suffix);
+        if Path::new(server_path).join(&folder).exists() { return Err(format!("A folder named '{}' already exists", folder)); }
+    }
+    Ok(())
+}
+
+fn copy_world_folder(source: &Path, dest: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let rel = entry.path().strip_prefix(source).map_err(|e| e.to_string())?;
+        let out_path = dest.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+        } else if entry.file_type().is_file() && !(entry.depth() == 1 && (entry.file_name() == "session.lock" || entry.file_name() == "uid.dat")) {
+            fs::copy(entry.path(), &out_path).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies a world and its dimension folders under a new name. Bukkit's `uid.dat` is left out so
+/// the copy is not rejected as a duplicate of the original. Returns the folders created.
+#[tauri::command]
+pub async fn clone_world(server_path: String, world_name: String, new_name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    check_world_pair(&server_path, &world_name, &new_name)?;
+    ensure_world_unloaded(&state, &server_path, &world_name)?;
+    let root = Path::new(&server_path);
+    let mut created = Vec::new();
+    for folder in world_folders(&server_path, &world_name) {
+        let target = format!("{}{}", new_name, &folder[world_name.len()..]);
+        if let Err(e) = copy_world_folder(&root.join(&folder), &root.join(&target)) {
+            for done in created.iter().chain(std::iter::once(&target)) { let _ = fs::remove_dir_all(root.join(done)); }
+            return Err(e);
+        }
+        created.push(target);
+    }
+    Ok(created)
+}
+
+/// Renames a world and its dimension folders, moving `level-name` along if it is the active world.
+#[tauri::command]
+pub async fn rename_world(server_path: String, world_name: String, new_name: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    check_world_pair(&server_path, &world_name, &new_name)?;
+    ensure_world_unloaded(&state, &server_path, &world_name)?;
+    let root = Path::new(&server_path);
+    let was_active = active_world_name(&server_path) == world_name;
+    let mut renamed: Vec<(String, String)> = Vec::new();
+    let mut result = Ok(());
+    for folder in world_folders(&server_path, &world_name) {
+        let target = format!("{}{}", new_name, &folder[world_name.len()..]);
+        result = fs::rename(root.join(&folder), root.join(&target)).map_err(|e| e.to_string());
+        if result.is_err() { break; }
+        renamed.push((folder, target));
+    }
+    if result.is_ok() && was_active { result = crate::set_property(&server_path, "level-name", &new_name); }
+    if let Err(e) = result {
+        // Put back what was already moved so the world is not left split across two names
+        for (folder, target) in renamed.iter().rev() { let _ = fs::rename(root.join(target), root.join(folder)); }
+        return Err(e);
+    }
+    Ok(renamed.into_iter().map(|(_, target)| target).collect())
+}
+
+/// Moves a world and its dimension folders into `roam_trash/<world>_<timestamp>` inside the
+/// server folder, so a mistaken delete can still be undone by hand. Returns the trash path.
+#[tauri::command]
+pub async fn delete_world(server_path: String, world_name: String, state: State<'_, AppState>) -> Result<String, String> {
+    check_world(&server_path, &world_name)?;
+    ensure_world_unloaded(&state, &server_path, &world_name)?;
+    let root = Path::new(&server_path);
+    let trash = root.join("roam_trash").join(format!("{}_{}", world_name, chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")));
+    fs::create_dir_all(&trash).map_err(|e| e.to_string())?;
+    let mut moved = Vec::new();
+    for folder in world_folders(&server_path, &world_name) {
+        if let Err(e) = fs::rename(root.join(&folder), trash.join(&folder)) {
+            // Bring back what was already moved so the world is not left half in the trash
+            for done in moved.iter().rev() { let _ = fs::rename(trash.join(done), root.join(done)); }
+            let _ = fs::remove_dir(&trash);
+            return Err(e.to_string());
+        }
+        moved.push(folder);
+    }
+    Ok(trash.to_string_lossy().to_string())
+}
@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::{Mapping, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::{AppState, ServerFlavor};
+
+/// Config files the editor knows about: `(file, first flavor that has it, only listed when present, description)`.
+const CONFIG_FILES: &[(&str, ServerFlavor, bool, &str)] = &[
+    ("bukkit.yml", ServerFlavor::Bukkit, false, "Spawn limits, ticks per spawn, chunk unloading and world generators."),
+    ("commands.yml", ServerFlavor::Bukkit, false, "Command aliases and overrides."),
+    ("spigot.yml", ServerFlavor::Spigot, false, "Entity activation and tracking ranges, merge radii, growth rates and BungeeCord."),
+    ("config/paper-global.yml", ServerFlavor::Paper, false, "Server-wide Paper settings: proxies, chunk loading, packet limits."),
+    ("config/paper-world-defaults.yml", ServerFlavor::Paper, false, "Paper defaults for every world: entity limits, anti-xray, redstone, despawn ranges."),
+    ("paper.yml", ServerFlavor::Paper, true, "Paper settings before 1.19, since split into the files in config/."),
+    ("purpur.yml", ServerFlavor::Paper, true, "Purpur gameplay settings."),
+];
+
+#[derive(Serialize, Debug)]
+pub struct ConfigFile {
+    pub file: &'static str,
+    pub description: &'static str,
+    /// Missing files are generated the next time the server starts.
+    pub exists: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConfigNode {
+    pub key: String,
+    pub path: Vec<String>,
+    /// `map`, `list`, `string`, `number`, `boolean` or `null`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The value for scalars and lists (edited as a whole); `None` for maps, see `children`.
+    pub value: Option<serde_json::Value>,
+    /// Comment lines directly above the key.
+    pub comment: Option<String>,
+    pub children: Vec<ConfigNode>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConfigChange {
+    pub path: Vec<String>,
+    pub value: serde_json::Value,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConfigWriteResult {
+    /// The server is running and only reads these files at startup.
+    pub restart_required: bool,
+    /// `false` when an edit could not be made in place and, with `force`, the file was rewritten
+    /// without comments.
+    pub comments_preserved: bool,
+}
+
+/// A `key:` line found by scanning the file as text.
+struct KeyLine {
+    path: Vec<String>,
+    line: usize,
+    indent: usize,
+    /// One past the last content line of the key's value.
+    end: usize,
+    comment: Option<String>,
+}
+
+fn config_path(server_path: &str, file: &str) -> Result<PathBuf, String> {
+    if !CONFIG_FILES.iter().any(|(name, ..)| *name == file) { return Err(format!("Unknown config file '{}'", file)); }
+    Ok(Path::new(server_path).join(file))
+}
+
+fn key_string(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        other => serde_yaml_ng::to_string(other).map(|s| s.trim_end().to_string()).unwrap_or_default(),
+    }
+}
+
+fn unquote(key: &str) -> String {
+    let key = key.trim();
+    if key.len() >= 2 && key.starts_with('\'') && key.ends_with('\'') { return key[1..key.len() - 1].replace("''", "'"); }
+    if key.len() >= 2 && key.starts_with('"') && key.ends_with('"') {
+        return serde_yaml_ng::from_str::<String>(key).unwrap_or_else(|_| key[1..key.len() - 1].to_string());
+    }
+    key.to_string()
+}
+
+/// Splits `key: value` at the first `:` followed by a space or the end of the line, outside a quoted key.
+fn split_key(content: &str) -> Option<(&str, &str)> {
+    let quote = content.chars().next().filter(|c| *c == '\'' || *c == '"');
+    let start = match quote {
+        Some(q) => content[1..].find(q)? + 2,
+        None => 0,
+    };
+    let colon = content[start..].char_indices()
+        .find(|(i, c)| *c == ':' && content[start + i + 1..].chars().next().is_none_or(|n| n == ' ' || n == '\t'))?.0 + start;
+    Some((&content[..colon], content[colon + 1..].trim_start()))
+}
+
+/// The ` # comment` at the end of an unquoted or quoted scalar, or `""`.
+fn trailing_comment(value: &str) -> &str {
+    let skip = match value.chars().next() {
+        Some(q @ ('\'' | '"')) => value[1..].find(q).map_or(value.len(), |i| i + 2),
+        _ => 0,
+    };
+    value[skip..].char_indices()
+        .find(|(i, c)| *c == '#' && (skip + i == 0 || value[..skip + i].ends_with([' ', '\t'])))
+        .map_or("", |(i, _)| &value[skip + i..])
+}
+
+/// Finds every mapping key in block-style YAML. Lists and block scalars are treated as opaque
+/// values, so nothing inside them is addressed.
+fn scan(lines: &[String]) -> Vec<KeyLine> {
+    let mut keys: Vec<KeyLine> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut comment: Vec<&str> = Vec::new();
+    let mut opaque_below: Option<usize> = None;
+    for (n, line) in lines.iter().enumerate() {
+        let content = line.trim_start();
+        let indent = line.len() - content.len();
+        if content.is_empty() { comment.clear(); continue; }
+        if let Some(limit) = opaque_below {
+            if indent > limit || (indent == limit && content.starts_with('-')) {
+                if !content.starts_with('#') { for &k in &stack { keys[k].end = n + 1; } }
+                continue;
+            }
+            opaque_below = None;
+        }
+        if content.starts_with('#') { comment.push(content.trim_start_matches('#').trim()); continue; }
+        if content == "-" || content.starts_with("- ") {
+            // A list may sit at the same indent as the key that owns it
+            while stack.last().is_some_and(|&k| keys[k].indent > indent) { stack.pop(); }
+            for &k in &stack { keys[k].end = n + 1; }
+            opaque_below = Some(indent);
+            comment.clear();
+            continue;
+        }
+        let Some((raw_key, value)) = split_key(content) else {
+            for &k in &stack { keys[k].end = n + 1; }
+            continue;
+        };
+        while stack.last().is_some_and(|&k| keys[k].indent >= indent) { stack.pop(); }
+        let mut path = stack.last().map(|&k| keys[k].path.clone()).unwrap_or_default();
+        path.push(unquote(raw_key));
+        for &k in &stack { keys[k].end = n + 1; }
+        keys.push(KeyLine { path, line: n, indent, end: n + 1, comment: (!comment.is_empty()).then(|| comment.join("\n")) });
+        comment.clear();
+        stack.push(keys.len() - 1);
+        if value.starts_with('|') || value.starts_with('>') { opaque_below = Some(indent); }
+    }
+    keys
+}
+
+fn render(value: &Value) -> Result<String, String> {
+    serde_yaml_ng::to_string(value).map(|s| s.trim_end().to_string()).map_err(|e| e.to_string())
+}
+
+/// `key: value` lines at `indent`, with non-empty maps and lists as an indented block.
+fn value_lines(indent: usize, key: &str, value: &Value, comment: &str) -> Result<Vec<String>, String> {
+    let pad = " ".repeat(indent);
+    let comment = if comment.is_empty() { String::new() } else { format!(" {}", comment) };
+    let block = match value {
+        Value::Mapping(m) => !m.is_empty(),
+        Value::Sequence(s) => !s.is_empty(),
+        _ => false,
+    };
+    if !block {
+        // Multi-line strings render as a `|-` block whose lines are already indented
+        let rendered = render(value)?;
+        let mut rendered = rendered.lines();
+        let mut lines = vec![format!("{}{}: {}{}", pad, key, rendered.next().unwrap_or_default(), comment)];
+        lines.extend(rendered.map(|l| format!("{}{}", pad, l)));
+        return Ok(lines);
+    }
+    let mut lines = vec![format!("{}{}:{}", pad, key, comment)];
+    lines.extend(render(value)?.lines().map(|l| format!("{}  {}", pad, l)));
+    Ok(lines)
+}
+
+/// Edits one value in the text, keeping comments and layout. Returns `false` when the change
+/// needs a structure the text edit cannot produce.
+fn apply_in_place(lines: &mut Vec<String>, path: &[String], value: &Value) -> Result<bool, String> {
+    let keys = scan(lines);
+    if let Some(key) = keys.iter().find(|k| k.path == path) {
+        let content = &lines[key.line][key.indent..];
+        let Some((raw_key, old)) = split_key(content) else { return Ok(false); };
+        let is_scalar = !matches!(value, Value::Mapping(m) if !m.is_empty()) && !matches!(value, Value::Sequence(s) if !s.is_empty());
+        // Keep the comment after an inline value, unless the new value is a block
+        let comment = if is_scalar { trailing_comment(old) } else { "" };
+        let replacement = value_lines(key.indent, raw_key, value, comment)?;
+        lines.splice(key.line..key.end, replacement);
+        return Ok(true);
+    }
+    let parent = (1..path.len()).rev().find_map(|len| keys.iter().find(|k| k.path == path[..len]));
+    let (at, indent, depth) = match parent {
+        Some(p) => {
+            // Only a key with nothing after it (a block map) can take new children
+            let inline = split_key(&lines[p.line][p.indent..]).map(|(_, v)| v[..v.len() - trailing_comment(v).len()].trim());
+            if inline.is_some_and(|v| !v.is_empty()) { return Ok(false); }
+            let sibling = keys.iter().find(|k| k.path.len() == p.path.len() + 1 && k.path.starts_with(&p.path)).map(|k| k.indent);
+            (p.end, sibling.unwrap_or(p.indent + 2), p.path.len())
+        }
+        None => (lines.len(), 0, 0),
+    };
+    let mut inserted = Vec::new();
+    for (i, segment) in path[depth..path.len() - 1].iter().enumerate() {
+        inserted.push(format!("{}{}:", " ".repeat(indent + 2 * i), render(&Value::String(segment.clone()))?));
+    }
+    let last = render(&Value::String(path[path.len() - 1].clone()))?;
+    inserted.extend(value_lines(indent + 2 * (path.len() - 1 - depth), &last, value, "")?);
+    lines.splice(at..at, inserted);
+    Ok(true)
+}
+
+fn set_path(root: &mut Value, path: &[String], value: Value) -> Result<(), String> {
+    let mut node = root;
+    for (i, segment) in path.iter().enumerate() {
+        if node.is_null() { *node = Value::Mapping(Mapping::new()); }
+        let Value::Mapping(map) = node else { return Err(format!("'{}' is not a section", path[..i].join("."))); };
+        let key = map.keys().find(|k| key_string(k) == *segment).cloned().unwrap_or_else(|| Value::String(segment.clone()));
+        if i == path.len() - 1 {
+            map.insert(key, value);
+            return Ok(());
+        }
+        node = map.entry(key).or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+/// Applies the changes to the text when every edit can be made in place and the result parses
+/// to the intended values; otherwise re-serializes the whole document.
+fn apply_changes(content: &str, changes: &[ConfigChange]) -> Result<(String, bool), String> {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut expected: Value = serde_yaml_ng::from_str(content).map_err(|e| e.to_string())?;
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let mut in_place = true;
+    for change in changes {
+        if change.path.is_empty() { return Err("Config path cannot be empty".into()); }
+        let value = serde_yaml_ng::to_value(&change.value).map_err(|e| e.to_string())?;
+        set_path(&mut expected, &change.path, value.clone())?;
+        in_place = in_place && apply_in_place(&mut lines, &change.path, &value)?;
+    }
+    if in_place {
+        let text = lines.join(newline) + newline;
+        if serde_yaml_ng::from_str::<Value>(&text).is_ok_and(|v| v == expected) { return Ok((text, true)); }
+    }
+    Ok((serde_yaml_ng::to_string(&expected).map_err(|e| e.to_string())?, false))
+}
+
+fn to_nodes(value: &Value, parent: &[String], comments: &HashMap<Vec<String>, String>) -> Vec<ConfigNode> {
+    let Value::Mapping(map) = value else { return Vec::new(); };
+    map.iter().map(|(k, v)| {
+        let key = key_string(k);
+        let mut path = parent.to_vec();
+        path.push(key.clone());
+        let kind = match v {
+            Value::Mapping(_) => "map",
+            Value::Sequence(_) => "list",
+            Value::Number(_) => "number",
+            Value::Bool(_) => "boolean",
+            Value::Null => "null",
+            Value::String(_) | Value::Tagged(_) => "string",
+        };
+        let value = if kind == "map" { None } else { serde_json::to_value(v).ok() };
+        ConfigNode { children: to_nodes(v, &path, comments), comment: comments.get(&path).cloned(), key, path, kind, value }
+    }).collect()
+}
+
+/// Config files that apply to the server's flavor (Bukkit < Spigot < Paper).
+#[tauri::command]
+pub fn list_config_files(path: String, state: State<'_, AppState>) -> Vec<ConfigFile> {
+    let jar_name = state.config.lock().unwrap().as_ref().filter(|c| c.path == path).map(|c| c.jar_name.clone());
+    let flavor = crate::detect_server_flavor(&path, jar_name.as_deref());
+    CONFIG_FILES.iter()
+        .filter(|(_, since, ..)| flavor >= *since)
+        .map(|(file, _, optional, description)| (file, optional, description, Path::new(&path).join(file).exists()))
+        .filter(|(_, optional, _, exists)| *exists || !**optional)
+        .map(|(file, _, description, exists)| ConfigFile { file, description, exists })
+        .collect()
+}
+
+/// Reads a config file as a tree, with the comments above each key.
+#[tauri::command]
+pub async fn read_config_file(path: String, file: String) -> Result<Vec<ConfigNode>, String> {
+    let content = fs::read_to_string(config_path(&path, &file)?).map_err(|e| e.to_string())?;
+    let value: Value = serde_yaml_ng::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+    let lines: Vec<String> = content.lines().map(String::from).collect();
+    let comments = scan(&lines).into_iter().filter_map(|k| Some((k.path, k.comment?))).collect();
+    Ok(to_nodes(&value, &[], &comments))
+}
+
+/// Sets values by key path, editing the file in place so comments and layout survive. When an
+/// edit cannot be made in place the file is left alone unless `force` allows rewriting it
+/// without comments.
+#[tauri::command]
+pub async fn write_config_values(path: String, file: String, changes: Vec<ConfigChange>, force: Option<bool>, state: State<'_, AppState>) -> Result<ConfigWriteResult, String> {
+    let config = config_path(&path, &file)?;
+    if !config.exists() { return Err(format!("{} does not exist yet. Start the server once to generate it.", file)); }
+    let content = fs::read_to_string(&config).map_err(|e| e.to_string())?;
+    let (text, comments_preserved) = apply_changes(&content, &changes).map_err(|e| format!("Failed to update {}: {}", file, e))?;
+    if !comments_preserved && !force.unwrap_or(false) {
+        return Err(format!("{} cannot be changed this way without removing its comments. Nothing was written.", file));
+    }
+    fs::write(&config, text).map_err(|e| e.to_string())?;
+    Ok(ConfigWriteResult { restart_required: crate::is_server_running(&state, &path), comments_preserved })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    fn set(text: &str, path: &[&str], value: Value) -> (String, bool) {
+        let mut lines = lines(text);
+        let path: Vec<String> = path.iter().map(|s| s.to_string()).collect();
+        let done = apply_in_place(&mut lines, &path, &value).unwrap();
+        (lines.join("\n") + "\n", done)
+    }
+
+    const SPIGOT: &str = "# Spigot settings\nsettings:\n  # Proxy support\n  bungeecord: false # off\n  restart-script: ./start.sh\n  'quoted key': 1\nworld-settings:\n  default:\n    merge-radius:\n      item: 2.5\n    hopper-amount: 1\ncommands:\n  spam-exclusions:\n  - /skill\n  - /home\n  tab-complete: 0\n";
+
+    #[test]
+    fn scan_finds_nested_keys_and_comments() {
+        let keys = scan(&lines(SPIGOT));
+        let paths: Vec<String> = keys.iter().map(|k| k.path.join(".")).collect();
+        assert_eq!(paths, [
+            "settings", "settings.bungeecord", "settings.restart-script", "settings.quoted key",
+            "world-settings", "world-settings.default", "world-settings.default.merge-radius",
+            "world-settings.default.merge-radius.item", "world-settings.default.hopper-amount",
+            "commands", "commands.spam-exclusions", "commands.tab-complete",
+        ]);
+        let find = |path: &str| keys.iter().find(|k| k.path.join(".") == path).unwrap();
+        assert_eq!(find("settings").comment.as_deref(), Some("Spigot settings"));
+        assert_eq!(find("settings.bungeecord").comment.as_deref(), Some("Proxy support"));
+        assert_eq!(find("settings").end, 6);
+        assert_eq!(find("world-settings.default").end, 11);
+    }
+
+    #[test]
+    fn scan_treats_block_lists_as_one_value() {
+        let keys = scan(&lines(SPIGOT));
+        let list = keys.iter().find(|k| k.path.join(".") == "commands.spam-exclusions").unwrap();
+        assert_eq!((list.line, list.end), (12, 15));
+        let keys = scan(&lines("worlds:\n  - name: world\n    seed: 1\nafter: true\n"));
+        let paths: Vec<String> = keys.iter().map(|k| k.path.join(".")).collect();
+        assert_eq!(paths, ["worlds", "after"]);
+    }
+
+    #[test]
+    fn replaces_scalars_and_keeps_trailing_comments() {
+        let (text, done) = set(SPIGOT, &["settings", "bungeecord"], Value::Bool(true));
+        assert!(done);
+        assert_eq!(text, SPIGOT.replace("bungeecord: false # off", "bungeecord: true # off"));
+        let (text, _) = set(SPIGOT, &["world-settings", "default", "merge-radius", "item"], serde_yaml_ng::to_value(4.0).unwrap());
+        assert!(text.contains("      item: 4.0\n    hopper-amount: 1\n"));
+        let (text, _) = set(SPIGOT, &["settings", "quoted key"], serde_yaml_ng::to_value(2).unwrap());
+        assert!(text.contains("  'quoted key': 2\n"));
+    }
+
+    #[test]
+    fn replaces_block_lists() {
+        let list = serde_yaml_ng::to_value(["/spawn"]).unwrap();
+        let (text, done) = set(SPIGOT, &["commands", "spam-exclusions"], list);
+        assert!(done);
+        assert!(text.ends_with("commands:\n  spam-exclusions:\n    - /spawn\n  tab-complete: 0\n"));
+        let (text, _) = set(SPIGOT, &["commands", "spam-exclusions"], Value::Sequence(Vec::new()));
+        assert!(text.ends_with("commands:\n  spam-exclusions: []\n  tab-complete: 0\n"));
+    }
+
+    #[test]
+    fn inserts_missing_keys_under_their_parent() {
+        let (text, done) = set(SPIGOT, &["world-settings", "default", "view-distance"], serde_yaml_ng::to_value(8).unwrap());
+        assert!(done);
+        assert!(text.contains("    hopper-amount: 1\n    view-distance: 8\ncommands:\n"));
+        let (text, _) = set(SPIGOT, &["world-settings", "world_nether", "arrow-despawn-rate"], serde_yaml_ng::to_value(300).unwrap());
+        assert!(text.contains("    hopper-amount: 1\n  world_nether:\n    arrow-despawn-rate: 300\ncommands:\n"));
+        let (text, _) = set(SPIGOT, &["messages", "whitelist"], Value::String("Not today".into()));
+        assert!(text.ends_with("  tab-complete: 0\nmessages:\n  whitelist: Not today\n"));
+    }
+
+    #[test]
+    fn refuses_children_under_an_inline_value() {
+        let (_, done) = set("settings: {debug: false}\n", &["settings", "verbose"], Value::Bool(true));
+        assert!(!done);
+    }
+
+    #[test]
+    fn apply_changes_falls_back_without_comments() {
+        let change = |path: &[&str], value: serde_json::Value| ConfigChange { path: path.iter().map(|s| s.to_string()).collect(), value };
+        let (text, preserved) = apply_changes(SPIGOT, &[change(&["settings", "bungeecord"], serde_json::json!(true))]).unwrap();
+        assert!(preserved);
+        assert!(text.starts_with("# Spigot settings\n"));
+        let (text, preserved) = apply_changes("flow: {a: 1} # note\n", &[change(&["flow", "b"], serde_json::json!(2))]).unwrap();
+        assert!(!preserved);
+        assert_eq!(serde_yaml_ng::from_str::<Value>(&text).unwrap(), serde_yaml_ng::from_str::<Value>("flow: {a: 1, b: 2}").unwrap());
+    }
+}
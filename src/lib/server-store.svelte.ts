@@ -1,16 +1,17 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import type { ServerConfig, ServerStats, ServerProperties, ServerStatus, PlayerInfo, WorldInfo } from "./types";
+import type { ServerConfig, ServerStats, ServerProperties, PropertySchema, ServerStatus, PlayerInfo, WorldInfo, ResourcePackResult, RetentionPolicy, ConfigFile, ConfigNode, ConfigWriteResult } from "./types";
 
 const isTauri = () => !!(window as any).__TAURI_INTERNALS__;
 
 class ServerStore {
   servers = $state<ServerConfig[]>([]);
   config = $state<ServerConfig | null>(null);
-  stats = $state<ServerStats>({ cpu: 0, core_count: 1, memory: 0, status: "Offline", player_count: 0, tunnel_status: "Offline" });
+  stats = $state<ServerStats>({ cpu: 0, core_count: 1, memory: 0, status: "Offline", player_count: 0, players: [], tunnel_status: "Offline" });
   players = $state<PlayerInfo[]>([]);
   worlds = $state<WorldInfo[]>([]);
   properties = $state<ServerProperties>({});
+  propertySchema = $state<PropertySchema[]>([]);
   logs = $state<string[]>([]);
   isDownloading = $state(false);
   
@@ -52,6 +53,35 @@ class ServerStore {
     }
   }
 
+  async setActiveWorld(worldName: string) {
+    if (this.config && isTauri()) {
+      try {
+        await invoke("set_active_world", { serverPath: this.config.path, worldName });
+        this.logs = [...this.logs.slice(-500), `[System] Active world set to: ${worldName}`];
+        await this.refreshWorlds();
+        await this.refreshProperties();
+      } catch (e) {
+        this.logs = [...this.logs.slice(-500), `[System] Could not switch world: ${e}`];
+        throw e;
+      }
+    }
+  }
+
+  async manageWorld(command: "clone_world" | "rename_world" | "delete_world", worldName: string, newName?: string) {
+    if (this.config && isTauri()) {
+      try {
+        const result = await invoke(command, { serverPath: this.config.path, worldName, newName });
+        const summary = Array.isArray(result) ? result.join(", ") : result;
+        this.logs = [...this.logs.slice(-500), `[System] ${command.replace("_world", "")} ${worldName}: ${summary}`];
+        await this.refreshWorlds();
+        await this.refreshProperties();
+      } catch (e) {
+        this.logs = [...this.logs.slice(-500), `[System] Could not ${command.replace("_world", "")} ${worldName}: ${e}`];
+        throw e;
+      }
+    }
+  }
+
   async refreshPlayers() {
     if (this.config && isTauri()) {
       try {
@@ -70,8 +100,9 @@ class ServerStore {
       this.logs = [...this.logs.slice(-500), event.payload];
     });
 
-    await listen<number>("player-update", (event) => {
-      this.stats.player_count = event.payload;
+    await listen<string[]>("player-update", (event) => {
+      this.stats.players = event.payload;
+      this.stats.player_count = event.payload.length;
     });
 
     await listen<ServerStatus>("status-update", (event) => {
@@ -101,6 +132,49 @@ class ServerStore {
     }
   }
 
+  async updateRetention(retention: RetentionPolicy | undefined) {
+    if (this.config) {
+      const updatedConfig = { ...this.config, retention };
+      const index = this.servers.findIndex(s => s.path === this.config?.path);
+      if (index !== -1) {
+        this.servers[index] = updatedConfig;
+        this.config = updatedConfig;
+        this.saveServers();
+        if (isTauri()) {
+          await invoke("set_server_config", { config: updatedConfig });
+        }
+      }
+    }
+  }
+
+  async updateProfileLookups(enabled: boolean) {
+    if (this.config) {
+      const updatedConfig = { ...this.config, profile_lookups: enabled };
+      const index = this.servers.findIndex(s => s.path === this.config?.path);
+      if (index !== -1) {
+        this.servers[index] = updatedConfig;
+        this.config = updatedConfig;
+        this.saveServers();
+        if (isTauri()) {
+          await invoke("set_server_config", { config: updatedConfig });
+        }
+      }
+    }
+  }
+
+  async setResourcePack(source: string, options: { externalUrl?: string; servePort?: number; publicHost?: string; require: boolean }) {
+    if (!this.config || !isTauri()) return;
+    const result = await invoke<ResourcePackResult>("set_resource_pack", { serverPath: this.config.path, source, ...options });
+    const updatedConfig = { ...this.config, resource_pack_port: result.served_port };
+    const index = this.servers.findIndex(s => s.path === this.config?.path);
+    if (index !== -1) this.servers[index] = updatedConfig;
+    this.config = updatedConfig;
+    this.saveServers();
+    this.logs = [...this.logs.slice(-500), `[System] Resource pack set: ${result.url} (sha1 ${result.sha1})`];
+    await this.refreshProperties();
+    return result;
+  }
+
   async loadServers() {
     try {
       const saved = localStorage.getItem("mc_servers");
@@ -187,18 +261,41 @@ class ServerStore {
     if (this.config && isTauri()) {
       const props = await invoke("read_properties", { path: this.config.path });
       this.properties = props as ServerProperties;
+      this.propertySchema = await invoke<PropertySchema[]>("get_properties_schema", { path: this.config.path });
     }
   }
 
   async saveProperties(props: ServerProperties) {
     if (this.config) {
-      this.properties = props;
+      const previous = this.properties;
       if (isTauri()) {
-        await invoke("write_properties", { path: this.config.path, props });
+        // Only send what changed so edits made by other tools survive
+        const set = Object.fromEntries(Object.entries(props).filter(([key, value]) => previous[key] !== value));
+        const remove = Object.keys(previous).filter((key) => !(key in props));
+        await invoke("patch_properties", { path: this.config.path, set, remove });
       }
+      this.properties = props;
     }
   }
 
+  async listConfigFiles() {
+    if (!this.config || !isTauri()) return [];
+    return await invoke<ConfigFile[]>("list_config_files", { path: this.config.path });
+  }
+
+  async readConfigFile(file: string) {
+    if (!this.config || !isTauri()) return [];
+    return await invoke<ConfigNode[]>("read_config_file", { path: this.config.path, file });
+  }
+
+  async writeConfigValues(file: string, changes: { path: string[]; value: unknown }[], force = false) {
+    if (!this.config || !isTauri()) return;
+    const result = await invoke<ConfigWriteResult>("write_config_values", { path: this.config.path, file, changes, force });
+    if (!result.comments_preserved) this.logs = [...this.logs.slice(-500), `[System] ${file} was rewritten without its comments.`];
+    if (result.restart_required) this.logs = [...this.logs.slice(-500), `[System] ${file} updated. Restart the server to apply it.`];
+    return result;
+  }
+
   async sendCommand(command: string) {
     if (this.stats.status === "Running" && isTauri()) {
       try {
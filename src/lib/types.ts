@@ -11,6 +11,28 @@ export interface ServerConfig {
   min_ram: string;
   max_ram: string;
   tunnel?: TunnelConfig;
+  retention?: RetentionPolicy;
+  backup_schedule?: BackupSchedule;
+  resource_pack_port?: number | null;
+  profile_api_url?: string | null;
+  profile_lookups?: boolean;
+}
+
+export interface BackupSchedule {
+  enabled: boolean;
+  worlds: string[];
+  interval_minutes?: number;
+  cron?: string;
+  backup_when_idle?: boolean;
+  incremental?: boolean;
+  on_stop?: boolean;
+}
+
+export interface RetentionPolicy {
+  keep_last?: number;
+  keep_daily_days?: number;
+  keep_weekly_weeks?: number;
+  max_total_mb?: number;
 }
 
 export interface ServerStats {
@@ -19,11 +41,24 @@ export interface ServerStats {
   memory: number;
   status: "Offline" | "Starting" | "Running" | "Stopping";
   player_count: number;
+  players: string[];
   tunnel_status: "Offline" | "Connecting" | "Online" | "Error";
 }
 
 export type ServerProperties = Record<string, string>;
 
+export interface PropertySchema {
+  key: string;
+  type: "boolean" | "integer" | "enum" | "string";
+  allowed: string[];
+  min: number | null;
+  max: number | null;
+  default: string;
+  description: string;
+  requires_restart: boolean;
+  new_world_only: boolean;
+}
+
 export interface PlayerInfo {
   uuid: string;
   name: string;
@@ -31,8 +66,310 @@ export interface PlayerInfo {
   steps: number;
 }
 
+export interface LevelInfo {
+  level_name?: string;
+  seed?: string;
+  version_name?: string;
+  data_version?: number;
+  game_mode?: string;
+  difficulty?: string;
+  hardcore: boolean;
+  spawn?: [number, number, number];
+  day_time?: number;
+  last_played?: string;
+  enabled_datapacks: string[];
+  disabled_datapacks: string[];
+  game_rules: Record<string, string>;
+}
+
 export interface WorldInfo {
   name: string;
   size_mb: number;
   last_modified: string;
+  active: boolean;
+  dimension_of?: string;
+  level?: LevelInfo;
+  newer_than_server: boolean;
+  level_error?: string;
+}
+
+export interface BackupInfo {
+  file_name: string;
+  world: string;
+  timestamp: string;
+  size_mb: number;
+  checksum: string;
+}
+
+export interface SnapshotInfo {
+  id: string;
+  world: string;
+  timestamp: string;
+  file_count: number;
+  size_mb: number;
+}
+
+export interface SnapshotResult {
+  id: string;
+  new_chunks: number;
+  reused_chunks: number;
+  stored_mb: number;
+}
+
+export interface BackupEvent {
+  server_path: string;
+  world: string;
+  trigger: string;
+  success: boolean;
+  result: string;
+  timestamp: string;
+}
+
+export interface WorldSettingsUpdate {
+  game_rules?: Record<string, string>;
+  difficulty?: "peaceful" | "easy" | "normal" | "hard";
+  spawn?: [number, number, number];
+  world_border?: { center_x: number; center_z: number; size: number };
+  hardcore?: boolean;
+  level_name?: string;
+}
+
+export interface TransferProgress {
+  world: string;
+  stage: string;
+  done_bytes: number;
+  total_bytes: number;
+}
+
+export interface CorruptChunk {
+  region: string;
+  chunk_x: number;
+  chunk_z: number;
+  reason: string;
+}
+
+export interface DimensionReport {
+  id: string;
+  path: string;
+  size_mb: number;
+  region_count: number;
+  chunk_count: number;
+  corrupt_chunks: CorruptChunk[];
+  heatmap: {
+    min_region_x: number;
+    min_region_z: number;
+    cell_regions: number;
+    width: number;
+    height: number;
+    cells: number[];
+  };
+}
+
+export interface WorldAnalysis {
+  world: string;
+  total_size_mb: number;
+  dimensions: DimensionReport[];
+}
+
+export interface TrimOptions {
+  min_inhabited_ticks: number | null;
+  max_radius_blocks: number | null;
+  dimensions: string[] | null;
+  dry_run: boolean;
+}
+
+export interface TrimReport {
+  dry_run: boolean;
+  backups: string[];
+  regions_deleted: number;
+  chunks_deleted: number;
+  chunks_kept: number;
+  chunks_unreadable: number;
+  files_skipped: string[];
+  freed_mb: number;
+}
+
+export interface DatapackInfo {
+  id: string;
+  file_name: string;
+  is_zip: boolean;
+  description: string | null;
+  pack_format: number | null;
+  enabled: boolean;
+  valid: boolean;
+}
+
+export interface ResourcePackResult {
+  url: string;
+  sha1: string;
+  served_port: number | null;
+}
+
+export interface WhitelistEntry {
+  uuid: string;
+  name: string;
+}
+
+export interface OpEntry {
+  uuid: string;
+  name: string;
+  level: number;
+  bypassesPlayerLimit: boolean;
+}
+
+export interface BanEntry {
+  uuid: string;
+  name: string;
+  created: string;
+  source: string;
+  expires: string;
+  reason: string;
+}
+
+export interface IpBanEntry {
+  ip: string;
+  created: string;
+  source: string;
+  expires: string;
+  reason: string;
+}
+
+export interface AccessLists {
+  whitelist_enabled: boolean;
+  whitelist: WhitelistEntry[];
+  ops: OpEntry[];
+  banned_players: BanEntry[];
+  banned_ips: IpBanEntry[];
+}
+
+export interface ResolvedProfile {
+  uuid: string;
+  name: string;
+  source: "usercache" | "offline" | "cache" | "api";
+}
+
+export interface StatValue {
+  key: string;
+  raw: number;
+  value: number;
+  unit: "count" | "m" | "h" | "hearts";
+}
+
+export interface PlayerStats {
+  uuid: string;
+  name: string;
+  data_version: number | null;
+  categories: Record<string, StatValue[]>;
+}
+
+export interface LeaderboardEntry {
+  rank: number;
+  uuid: string;
+  name: string;
+  raw: number;
+  value: number;
+  unit: StatValue["unit"];
+}
+
+export interface AdvancementProgress {
+  id: string;
+  category: string;
+  done: boolean;
+  completed_at: string | null;
+  criteria_done: number;
+  vanilla: boolean;
+}
+
+export interface CategoryProgress {
+  category: string;
+  completed: number;
+  total: number;
+  percent: number;
+}
+
+export interface PlayerAdvancements {
+  uuid: string;
+  name: string;
+  data_version: number | null;
+  categories: CategoryProgress[];
+  advancements: AdvancementProgress[];
+  missing: string[];
+}
+
+export interface AdvancementSummary {
+  uuid: string;
+  name: string;
+  completed: number;
+  total: number;
+  percent: number;
+}
+
+export interface Enchantment {
+  id: string;
+  level: number;
+}
+
+export interface ItemStack {
+  slot: string;
+  id: string;
+  count: number;
+  damage: number | null;
+  custom_name: string | null;
+  enchantments: Enchantment[];
+  stored_enchantments: Enchantment[];
+  data: Record<string, unknown> | null;
+}
+
+export interface PlayerData {
+  uuid: string;
+  name: string;
+  data_version: number | null;
+  last_saved: string | null;
+  dimension: string;
+  position: [number, number, number] | null;
+  health: number | null;
+  food_level: number | null;
+  xp_level: number | null;
+  xp_progress: number | null;
+  xp_total: number | null;
+  game_mode: string | null;
+  selected_slot: number | null;
+  inventory: ItemStack[];
+  armor: ItemStack[];
+  offhand: ItemStack | null;
+  ender_chest: ItemStack[];
+}
+
+export interface Session {
+  player: string;
+  joined: string;
+  left: string | null;
+  host: string | null;
+  minutes: number;
+}
+
+export interface PeakPlayers {
+  peak: number;
+  at: string | null;
+  players: string[];
+}
+
+export interface ConfigFile {
+  file: string;
+  description: string;
+  exists: boolean;
+}
+
+export interface ConfigNode {
+  key: string;
+  path: string[];
+  type: "map" | "list" | "string" | "number" | "boolean" | "null";
+  value: unknown;
+  comment: string | null;
+  children: ConfigNode[];
+}
+
+export interface ConfigWriteResult {
+  restart_required: boolean;
+  comments_preserved: boolean;
 }